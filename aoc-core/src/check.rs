@@ -0,0 +1,229 @@
+//! Cross-checking of the implementations of a day on random inputs.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use proptest::{
+    strategy::Strategy,
+    test_runner::{Config, TestCaseError, TestError, TestRunner},
+};
+
+use crate::{num::SolveError, Day, Part};
+
+/// Solves `input` with every implementation of `day` and fails unless the
+/// implementations of each part agree, on the answer or on the error. An
+/// implementation that overflows is left out, a wider one may still have the
+/// answer.
+pub fn agree(day: &Day, input: &str) -> Result<(), String> {
+    let parsed = day.parse(input).map_err(|err| err.to_string())?;
+    for part in [Part::One, Part::Two] {
+        let answers = day
+            .part(part)
+            .map(|imp| (imp.name, imp.solve(&parsed)))
+            .filter(|(_, answer)| *answer != Err(SolveError::Overflow))
+            .collect::<Vec<(&str, Result<String, SolveError>)>>();
+        let Some((first, expected)) = answers.first() else {
+            continue;
+        };
+        if let Some((name, answer)) = answers.iter().find(|(_, answer)| answer != expected) {
+            return Err(format!(
+                "part {}: {} but {}",
+                part,
+                outcome(first, expected),
+                outcome(name, answer)
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn outcome(name: &str, answer: &Result<String, SolveError>) -> String {
+    match answer {
+        Ok(answer) => format!("{} answers {}", name, answer),
+        Err(err) => format!("{} fails with {}", name, err),
+    }
+}
+
+/// Runs [`agree`] on random `inputs`. The smallest failing input proptest
+/// shrinks down to is saved in `dir` as the next free `input.dev.propN.txt`,
+/// which [`check_fixtures`] picks up from then on.
+pub fn cross_check(day: &Day, dir: &Path, inputs: impl Strategy<Value = String>) {
+    let mut runner = TestRunner::new(Config {
+        failure_persistence: None,
+        ..Config::default()
+    });
+    match runner.run(&inputs, |input| {
+        agree(day, &input).map_err(TestCaseError::fail)
+    }) {
+        Ok(()) => {}
+        Err(TestError::Fail(reason, input)) => {
+            let path = save_fixture(dir, &input);
+            panic!(
+                "day {}: {}\nsaved the input to {}:\n{}",
+                day.day,
+                reason,
+                path.display(),
+                input
+            );
+        }
+        Err(TestError::Abort(reason)) => panic!("day {}: {}", day.day, reason),
+    }
+}
+
+/// Runs [`agree`] on every input saved by [`cross_check`].
+pub fn check_fixtures(day: &Day, dir: &Path) {
+    for path in fixtures(dir) {
+        let input = fs::read_to_string(&path).expect("Cannot read fixture");
+        if let Err(reason) = agree(day, &input) {
+            panic!("day {}: {}: {}", day.day, path.display(), reason);
+        }
+    }
+}
+
+fn fixtures(dir: &Path) -> Vec<PathBuf> {
+    let mut paths = fs::read_dir(dir)
+        .expect("Cannot read fixture directory")
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| name.starts_with("input.dev.prop") && name.ends_with(".txt"))
+        })
+        .collect::<Vec<PathBuf>>();
+    paths.sort();
+    paths
+}
+
+fn save_fixture(dir: &Path, input: &str) -> PathBuf {
+    let path = (1..)
+        .map(|n| dir.join(format!("input.dev.prop{}.txt", n)))
+        .find(|path| !path.exists())
+        .unwrap();
+    fs::write(&path, input).expect("Cannot write fixture");
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        env,
+        panic::{catch_unwind, AssertUnwindSafe},
+    };
+
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::{num::Answer, Alternative, ParseError, Solution};
+
+    /// Sums numbers, with a broken alternative that ignores the ones above 50.
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 0;
+        type Input = Vec<u32>;
+        type Answer1 = u32;
+        type Answer2 = u32;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input
+                .lines()
+                .map(|line| {
+                    line.parse()
+                        .map_err(|_| ParseError::at(0, input, line, "a number"))
+                })
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> u32 {
+            input.iter().sum()
+        }
+
+        fn part2(input: &Self::Input) -> u32 {
+            input.iter().sum()
+        }
+
+        fn alternatives() -> Vec<Alternative<Self::Input>> {
+            vec![Alternative {
+                part: Part::Two,
+                name: "part2_small",
+                solve: |input| input.iter().filter(|&&n| n <= 50).sum::<u32>().answer(),
+            }]
+        }
+    }
+
+    /// The largest number, with alternatives that answer 0 when there is none
+    /// and overflow on the sum.
+    struct Max;
+
+    impl Solution for Max {
+        const DAY: u8 = 0;
+        type Input = Vec<u32>;
+        type Answer1 = Result<u32, SolveError>;
+        type Answer2 = Result<u32, SolveError>;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            Sum::parse(input)
+        }
+
+        fn part1(input: &Self::Input) -> Result<u32, SolveError> {
+            let max = input.iter().max().copied();
+            max.ok_or(SolveError::NoAnswer("no numbers".to_string()))
+        }
+
+        fn part2(input: &Self::Input) -> Result<u32, SolveError> {
+            Ok(input.iter().sum())
+        }
+
+        fn alternatives() -> Vec<Alternative<Self::Input>> {
+            vec![
+                Alternative {
+                    part: Part::One,
+                    name: "part1_zero",
+                    solve: |input| input.iter().max().copied().unwrap_or(0).answer(),
+                },
+                Alternative {
+                    part: Part::Two,
+                    name: "part2_narrow",
+                    solve: |input| {
+                        let sum = input
+                            .iter()
+                            .try_fold(0u8, |sum, &n| sum.checked_add(u8::try_from(n).ok()?));
+                        sum.map(u32::from).ok_or(SolveError::Overflow).answer()
+                    },
+                },
+            ]
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let day = Day::of::<Max>();
+        assert_eq!(agree(&day, "200\n100\n"), Ok(()));
+        assert_eq!(
+            agree(&day, ""),
+            Err("part 1: part1 fails with no answer: no numbers but part1_zero answers 0".into())
+        );
+    }
+
+    #[test]
+    fn test_shrunk_fixture() {
+        let dir = env::temp_dir().join(format!("aoc-check-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let day = Day::of::<Sum>();
+        let inputs = prop::collection::vec(0..100u32, 1..10).prop_map(|numbers| {
+            numbers
+                .iter()
+                .map(|n| format!("{}\n", n))
+                .collect::<String>()
+        });
+        let result = catch_unwind(AssertUnwindSafe(|| cross_check(&day, &dir, inputs)));
+        assert!(result.is_err());
+        assert_eq!(fixtures(&dir), [dir.join("input.dev.prop1.txt")]);
+        let fixture = fs::read_to_string(dir.join("input.dev.prop1.txt")).unwrap();
+        assert_eq!(fixture, "51\n");
+        assert!(catch_unwind(AssertUnwindSafe(|| check_fixtures(&day, &dir))).is_err());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
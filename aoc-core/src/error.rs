@@ -0,0 +1,120 @@
+use std::{error::Error, fmt};
+
+use nom::error::{VerboseError, VerboseErrorKind};
+
+/// Bad puzzle input, pointing at the character where parsing stopped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub day: u8,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// The whole offending line.
+    pub snippet: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Error at byte `offset` of `input`.
+    pub fn new(day: u8, input: &str, offset: usize, expected: impl Into<String>) -> Self {
+        let offset = offset.min(input.len());
+        let line_start = input[..offset].rfind('\n').map_or(0, |indx| indx + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |indx| offset + indx);
+        ParseError {
+            day,
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            snippet: input[line_start..line_end].to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Error at the start of `rest`, which must be a slice of `input`.
+    pub fn at(day: u8, input: &str, rest: &str, expected: impl Into<String>) -> Self {
+        let offset = (rest.as_ptr() as usize).saturating_sub(input.as_ptr() as usize);
+        ParseError::new(day, input, offset, expected)
+    }
+
+    /// Converts a nom error raised while parsing a slice of `input`. The
+    /// innermost `context` becomes the expectation.
+    pub fn from_nom(day: u8, input: &str, err: nom::Err<VerboseError<&str>>) -> Self {
+        match err {
+            nom::Err::Error(err) | nom::Err::Failure(err) => {
+                let (rest, _) = err.errors[0];
+                let expected = err
+                    .errors
+                    .iter()
+                    .find_map(|(_, kind)| match kind {
+                        VerboseErrorKind::Context(context) => Some(context.to_string()),
+                        _ => None,
+                    })
+                    .unwrap_or_else(|| match &err.errors[0].1 {
+                        VerboseErrorKind::Char(c) => format!("`{}`", c),
+                        VerboseErrorKind::Nom(kind) => kind.description().to_lowercase(),
+                        VerboseErrorKind::Context(context) => context.to_string(),
+                    });
+                ParseError::at(day, input, rest, expected)
+            }
+            nom::Err::Incomplete(_) => ParseError::new(day, input, input.len(), "more input"),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "day {}: line {}, column {}: expected {}",
+            self.day, self.line, self.column, self.expected
+        )?;
+        // Control characters such as a stray `\r` are escaped so that they
+        // show up, the caret is shifted to match.
+        let snippet = self
+            .snippet
+            .chars()
+            .map(|c| c.escape_debug().to_string())
+            .collect::<Vec<String>>();
+        let caret = snippet
+            .iter()
+            .take(self.column - 1)
+            .map(|c| c.chars().count())
+            .sum::<usize>();
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", self.line, snippet.concat())?;
+        write!(f, "{} | {}^", gutter, " ".repeat(caret))
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_position() {
+        let input = "Game 1: 3 blue\nGame 2; 4 red\n";
+        let err = ParseError::new(2, input, 21, "`: `");
+        assert_eq!((err.line, err.column), (2, 7));
+        assert_eq!(err.snippet, "Game 2; 4 red");
+        assert_eq!(
+            err.to_string(),
+            "day 2: line 2, column 7: expected `: `\n  |\n2 | Game 2; 4 red\n  |       ^"
+        );
+    }
+
+    #[test]
+    fn test_escaped_carriage_return() {
+        let input = "ab\r\ncd";
+        let err = ParseError::at(15, input, &input[2..], "`,`");
+        assert_eq!((err.line, err.column), (1, 3));
+        assert_eq!(
+            err.to_string(),
+            "day 15: line 1, column 3: expected `,`\n  |\n1 | ab\\r\n  |   ^"
+        );
+    }
+}
@@ -0,0 +1,34 @@
+use std::fs;
+
+use crate::{Day, Part};
+
+/// An example input from the puzzle description and the answer given there.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Example {
+    pub part: Part,
+    /// Fixture file, relative to the day's crate.
+    pub file: &'static str,
+    pub answer: &'static str,
+}
+
+/// Checks every implementation of each example's part against its answer.
+pub fn assert_examples(day: &Day, examples: &[Example]) {
+    for example in examples {
+        let input = fs::read_to_string(example.file)
+            .unwrap_or_else(|err| panic!("cannot read {}: {}", example.file, err));
+        let parsed = day
+            .parse(&input)
+            .unwrap_or_else(|err| panic!("{}: {}", example.file, err));
+        for imp in day.part(example.part) {
+            assert_eq!(
+                imp.solve(&parsed).as_deref(),
+                Ok(example.answer),
+                "day {} part {} with {} on {}",
+                day.day,
+                example.part,
+                imp.name,
+                example.file
+            );
+        }
+    }
+}
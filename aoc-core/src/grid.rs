@@ -0,0 +1,343 @@
+use std::{
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+use crate::ParseError;
+
+/// A `(row, column)` position in a [`Grid`].
+pub type Pos = (usize, usize);
+
+pub const UP: (isize, isize) = (-1, 0);
+pub const RIGHT: (isize, isize) = (0, 1);
+pub const DOWN: (isize, isize) = (1, 0);
+pub const LEFT: (isize, isize) = (0, -1);
+
+/// Offsets to the up, right, down and left neighbours.
+pub const ORTHOGONAL: [(isize, isize); 4] = [UP, RIGHT, DOWN, LEFT];
+
+/// Offsets to all eight neighbours, orthogonal ones first.
+pub const ADJACENT: [(isize, isize); 8] = [
+    (-1, 0),
+    (0, 1),
+    (1, 0),
+    (0, -1),
+    (-1, -1),
+    (-1, 1),
+    (1, 1),
+    (1, -1),
+];
+
+/// A rectangle of cells stored row by row in a single `Vec`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Panics unless `cells` holds exactly `rows * cols` cells.
+    pub fn new(rows: usize, cols: usize, cells: Vec<T>) -> Grid<T> {
+        assert_eq!(cells.len(), rows * cols, "cells do not fill the grid");
+        Grid { rows, cols, cells }
+    }
+
+    pub fn filled(rows: usize, cols: usize, value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid::new(rows, cols, vec![value; rows * cols])
+    }
+
+    /// Reads one cell per character and one row per line of `input`.
+    pub fn parse(
+        day: u8,
+        input: &str,
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        Grid::parse_block(day, input, input, expected, cell)
+    }
+
+    /// Like [`Grid::parse`] for a `block` that is a slice of `input`, such as
+    /// one of several grids separated by blank lines. Errors point into
+    /// `input`.
+    pub fn parse_block(
+        day: u8,
+        input: &str,
+        block: &str,
+        expected: &str,
+        cell: impl Fn(char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let err = |rest: &str, expected: &str| ParseError::at(day, input, rest, expected);
+        let Some(cols) = block.lines().next().map(|line| line.chars().count()) else {
+            return Err(err(block, "a grid"));
+        };
+        let mut cells = vec![];
+        let mut rows = 0;
+        for line in block.lines() {
+            if line.is_empty() || line.chars().count() != cols {
+                let end = line
+                    .char_indices()
+                    .nth(cols)
+                    .map_or(line.len(), |(indx, _)| indx);
+                let expected = format!("a row of {} characters", cols);
+                return Err(err(&line[end..], &expected));
+            }
+            for (indx, c) in line.char_indices() {
+                cells.push(cell(c).ok_or_else(|| err(&line[indx..], expected))?);
+            }
+            rows += 1;
+        }
+        Ok(Grid::new(rows, cols, cells))
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    /// `pos` moved by `offset`, if that stays inside the grid.
+    pub fn offset(&self, pos: Pos, offset: (isize, isize)) -> Option<Pos> {
+        let row = pos.0.checked_add_signed(offset.0)?;
+        let col = pos.1.checked_add_signed(offset.1)?;
+        (row < self.rows && col < self.cols).then_some((row, col))
+    }
+
+    pub fn get(&self, row: isize, col: isize) -> Option<&T> {
+        let indx = self.indx(row, col)?;
+        Some(&self.cells[indx])
+    }
+
+    pub fn get_mut(&mut self, row: isize, col: isize) -> Option<&mut T> {
+        let indx = self.indx(row, col)?;
+        Some(&mut self.cells[indx])
+    }
+
+    fn indx(&self, row: isize, col: isize) -> Option<usize> {
+        let row = usize::try_from(row).ok().filter(|&row| row < self.rows)?;
+        let col = usize::try_from(col).ok().filter(|&col| col < self.cols)?;
+        Some(row * self.cols + col)
+    }
+
+    /// The in bounds positions among `offsets` from `pos`.
+    pub fn neighbours<'a>(
+        &'a self,
+        pos: Pos,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = Pos> + 'a {
+        offsets
+            .iter()
+            .filter_map(move |&offset| self.offset(pos, offset))
+    }
+
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbours(pos, &ORTHOGONAL)
+    }
+
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        self.neighbours(pos, &ADJACENT)
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, row: usize) -> &mut [T] {
+        &mut self.cells[row * self.cols..(row + 1) * self.cols]
+    }
+
+    pub fn col(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> {
+        assert!(col < self.cols, "column {} out of bounds", col);
+        self.cells.iter().skip(col).step_by(self.cols)
+    }
+
+    pub fn iter_rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        self.cells.chunks(self.cols.max(1))
+    }
+
+    pub fn iter_cols(
+        &self,
+    ) -> impl DoubleEndedIterator<Item = impl DoubleEndedIterator<Item = &T>> {
+        (0..self.cols).map(|col| self.col(col))
+    }
+
+    /// Every cell with its position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(indx, cell)| ((indx / self.cols, indx % self.cols), cell))
+    }
+
+    /// Positions of the cells matching `predicate`, row by row.
+    pub fn find_all<'a>(
+        &'a self,
+        predicate: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Pos> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.rows, self.cols, self.cells.iter().map(f).collect())
+    }
+
+    /// Builds a `rows` by `cols` grid, taking each cell from `from(row, col)`.
+    fn rebuild(&self, rows: usize, cols: usize, from: impl Fn(usize, usize) -> Pos) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cells = (0..rows)
+            .flat_map(|row| (0..cols).map(move |col| (row, col)))
+            .map(|(row, col)| self[from(row, col)].clone())
+            .collect();
+        Grid::new(rows, cols, cells)
+    }
+
+    /// Rows become columns.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        self.rebuild(self.cols, self.rows, |row, col| (col, row))
+    }
+
+    pub fn rotate_clockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let rows = self.rows;
+        self.rebuild(self.cols, self.rows, |row, col| (rows - 1 - col, row))
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cols = self.cols;
+        self.rebuild(self.cols, self.rows, |row, col| (col, cols - 1 - row))
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let cols = self.cols;
+        self.rebuild(self.rows, self.cols, |row, col| (row, cols - 1 - col))
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let rows = self.rows;
+        self.rebuild(self.rows, self.cols, |row, col| (rows - 1 - row, col))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): Pos) -> &T {
+        assert!(
+            row < self.rows && col < self.cols,
+            "{:?} out of bounds",
+            (row, col)
+        );
+        &self.cells[row * self.cols + col]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, (row, col): Pos) -> &mut T {
+        assert!(
+            row < self.rows && col < self.cols,
+            "{:?} out of bounds",
+            (row, col)
+        );
+        &mut self.cells[row * self.cols + col]
+    }
+}
+
+/// One line per row, cells written next to each other.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.iter_rows() {
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid(text: &str) -> Grid<char> {
+        Grid::parse(0, text, "a cell", Some).unwrap()
+    }
+
+    #[test]
+    fn test_access() {
+        let mut grid = grid("abc\ndef\n");
+        assert_eq!((grid.rows(), grid.cols()), (2, 3));
+        assert_eq!(grid.get(1, 2), Some(&'f'));
+        assert_eq!(grid.get(-1, 0), None);
+        assert_eq!(grid.get(0, 3), None);
+        *grid.get_mut(0, 0).unwrap() = 'A';
+        grid[(1, 0)] = 'D';
+        assert_eq!(grid.row(1), ['D', 'e', 'f']);
+        assert_eq!(grid.col(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.neighbours4((0, 0)).collect::<Vec<Pos>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbours8((0, 1)).count(), 5);
+        assert_eq!(
+            grid.find_all(|c| c.is_uppercase()).collect::<Vec<Pos>>(),
+            [(0, 0), (1, 0)]
+        );
+        assert_eq!(grid.to_string(), "Abc\nDef\n");
+    }
+
+    #[test]
+    fn test_transforms() {
+        let grid = grid("abc\ndef");
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc\n");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "#.#\n#x#\n";
+        let err = Grid::parse(0, input, "`.` or `#`", |c| {
+            (c == '.' || c == '#').then_some(c)
+        })
+        .unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (2, 2, "`.` or `#`")
+        );
+        let input = "x\n\n#.#\n#.\n";
+        let err = Grid::parse_block(0, input, &input[3..], "a cell", Some).unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (4, 3, "a row of 3 characters")
+        );
+    }
+}
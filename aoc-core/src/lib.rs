@@ -0,0 +1,73 @@
+#[cfg(feature = "check")]
+pub mod check;
+mod error;
+pub mod example;
+pub mod generate;
+pub mod grid;
+pub mod memo;
+pub mod num;
+pub mod parse;
+mod solution;
+pub mod stream;
+pub mod trace;
+
+use std::{
+    env,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    process,
+};
+
+pub use error::ParseError;
+pub use example::Example;
+pub use grid::Grid;
+use num::Answer;
+pub use solution::{Alternative, Day, Implementation, Input, Part, Solution};
+pub use stream::Streaming;
+
+/// Entry point shared by the per day binaries: solves both parts of the file
+/// named on the command line, `-` for stdin, or else of `path`.
+pub fn run<S: Solution>(path: &str) {
+    let mut input = String::new();
+    open(path)
+        .read_to_string(&mut input)
+        .expect("Cannot read input");
+    let parsed = S::parse(&input).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+    print(S::part1(&parsed), S::part2(&parsed));
+}
+
+/// Same as [`run`], reading the input one record at a time.
+pub fn run_stream<S: Streaming>(path: &str) {
+    let (part1, part2) = stream::solve::<S>(open(path)).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+    print(part1, part2);
+}
+
+fn print(part1: impl Answer, part2: impl Answer) {
+    for (part, answer) in [(1, part1.answer()), (2, part2.answer())] {
+        match answer {
+            Ok(answer) => println!("part{}: {}", part, answer),
+            Err(err) => {
+                eprintln!("part{}: {}", part, err);
+                process::exit(1);
+            }
+        }
+    }
+}
+
+fn open(path: &str) -> Box<dyn BufRead> {
+    let path = env::args().nth(1).unwrap_or_else(|| path.to_string());
+    if path == "-" {
+        return Box::new(io::stdin().lock());
+    }
+    let file = File::open(&path).unwrap_or_else(|err| {
+        eprintln!("cannot read {}: {}", path, err);
+        process::exit(1);
+    });
+    Box::new(BufReader::new(file))
+}
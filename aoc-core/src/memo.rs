@@ -0,0 +1,132 @@
+//! Memoization of recursive solvers: the result of each state is computed
+//! once, the recursion calling back into the same [`Memo`].
+
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
+
+use crate::trace::{self, Event};
+
+/// A lookup in a [`Memo`], emitted as a trace event.
+#[derive(Debug)]
+pub enum Lookup<'a, K> {
+    Hit(&'a K),
+    Miss(&'a K),
+}
+
+impl<K: Debug> Event for Lookup<'_, K> {
+    fn name(&self) -> &'static str {
+        match self {
+            Lookup::Hit(_) => "hit",
+            Lookup::Miss(_) => "miss",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Stats {
+    pub hits: usize,
+    pub misses: usize,
+    /// Results held.
+    pub len: usize,
+}
+
+/// Results keyed by the state they were computed from. A memo with a
+/// capacity stops taking in results once it holds that many, states beyond
+/// it are computed again each time.
+#[derive(Debug)]
+pub struct Memo<K, V> {
+    results: HashMap<K, V>,
+    capacity: Option<usize>,
+    hits: usize,
+    misses: usize,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Memo {
+            results: HashMap::new(),
+            capacity: None,
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<K: Hash + Eq + Debug, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Memo::default()
+    }
+
+    /// A memo holding at most `capacity` results.
+    pub fn bounded(capacity: usize) -> Self {
+        Memo {
+            capacity: Some(capacity),
+            ..Memo::default()
+        }
+    }
+
+    /// The result of `key`, computed by `compute` unless it is known. The
+    /// memo is handed to `compute` for the states it recurses into.
+    pub fn get_or_insert_with(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V {
+        if let Some(value) = self.results.get(&key) {
+            self.hits += 1;
+            trace::emit(|| Lookup::Hit(&key));
+            return value.clone();
+        }
+        self.misses += 1;
+        trace::emit(|| Lookup::Miss(&key));
+        let value = compute(self);
+        if self
+            .capacity
+            .is_none_or(|capacity| self.results.len() < capacity)
+        {
+            self.results.insert(key, value.clone());
+        }
+        value
+    }
+
+    pub fn stats(&self) -> Stats {
+        Stats {
+            hits: self.hits,
+            misses: self.misses,
+            len: self.results.len(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fibonacci(n: u64, memo: &mut Memo<u64, u64>) -> u64 {
+        memo.get_or_insert_with(n, |memo| match n {
+            0 | 1 => n,
+            _ => fibonacci(n - 1, memo) + fibonacci(n - 2, memo),
+        })
+    }
+
+    #[test]
+    fn test_memo() {
+        let mut memo = Memo::new();
+        assert_eq!(fibonacci(90, &mut memo), 2880067194370816120);
+        assert_eq!(
+            memo.stats(),
+            Stats {
+                hits: 88,
+                misses: 91,
+                len: 91
+            }
+        );
+        let (_, count) = trace::with(trace::Count::default(), || fibonacci(90, &mut memo));
+        assert_eq!(count.0, [("hit", 1)].into());
+    }
+
+    #[test]
+    fn test_bounded() {
+        let mut memo = Memo::bounded(10);
+        assert_eq!(fibonacci(20, &mut memo), 6765);
+        assert_eq!(memo.stats().len, 10);
+        // Beyond the bound the states are computed again, not remembered.
+        assert_eq!(fibonacci(20, &mut memo), 6765);
+        assert_eq!(memo.stats().len, 10);
+    }
+}
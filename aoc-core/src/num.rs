@@ -0,0 +1,219 @@
+//! Integer arithmetic that reports overflow instead of wrapping, for the
+//! numeric cores of the days. A core generic over [`Int`] runs in 64 bits by
+//! default and in 128 bits when the answer does not fit.
+
+use std::{
+    error::Error,
+    fmt::{self, Debug, Display},
+    ops::{Div, Rem},
+};
+
+/// An answer, or an intermediate value, did not fit in the integer type it
+/// was computed in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow;
+
+impl Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "arithmetic overflow")
+    }
+}
+
+impl Error for Overflow {}
+
+/// The integer types a numeric core can be computed in. Every operation that
+/// can overflow is checked.
+pub trait Int:
+    Copy
+    + Ord
+    + Default
+    + Debug
+    + Display
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + TryFrom<u64>
+    + TryFrom<u128>
+    + Send
+    + Sync
+    + 'static
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn try_add(self, other: Self) -> Result<Self, Overflow>;
+    fn try_sub(self, other: Self) -> Result<Self, Overflow>;
+    fn try_mul(self, other: Self) -> Result<Self, Overflow>;
+
+    /// `value`, of another integer type, in this one.
+    fn of<T>(value: T) -> Result<Self, Overflow>
+    where
+        Self: TryFrom<T>,
+    {
+        Self::try_from(value).map_err(|_| Overflow)
+    }
+
+    /// The sum of `values`, stopping at the first error or overflow.
+    fn try_sum<E: From<Overflow>>(
+        values: impl IntoIterator<Item = Result<Self, E>>,
+    ) -> Result<Self, E> {
+        values
+            .into_iter()
+            .try_fold(Self::ZERO, |sum, value| Ok(sum.try_add(value?)?))
+    }
+}
+
+macro_rules! int {
+    ($($int:ty),*) => {$(
+        impl Int for $int {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn try_add(self, other: Self) -> Result<Self, Overflow> {
+                self.checked_add(other).ok_or(Overflow)
+            }
+
+            fn try_sub(self, other: Self) -> Result<Self, Overflow> {
+                self.checked_sub(other).ok_or(Overflow)
+            }
+
+            fn try_mul(self, other: Self) -> Result<Self, Overflow> {
+                self.checked_mul(other).ok_or(Overflow)
+            }
+        }
+    )*};
+}
+
+int!(u32, u64, u128, usize, i32, i64, i128);
+
+/// The greatest common divisor of two non-negative numbers.
+pub fn gcd<N: Int>(a: N, b: N) -> N {
+    if b == N::ZERO {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// The least common multiple of two non-negative numbers, dividing before
+/// multiplying so that only an answer that does not fit overflows.
+pub fn lcm<N: Int>(a: N, b: N) -> Result<N, Overflow> {
+    if a == N::ZERO || b == N::ZERO {
+        return Ok(N::ZERO);
+    }
+    (a / gcd(a, b)).try_mul(b)
+}
+
+/// A running sum that keeps its first error, for the state of a
+/// [`Streaming`](crate::Streaming) day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Total<N, E = Overflow>(Result<N, E>);
+
+impl<N: Int, E> Default for Total<N, E> {
+    fn default() -> Self {
+        Total(Ok(N::ZERO))
+    }
+}
+
+impl<N: Int, E: From<Overflow>> Total<N, E> {
+    pub fn add(&mut self, value: Result<N, E>) {
+        if let Ok(sum) = self.0 {
+            self.0 = value.and_then(|value| Ok(sum.try_add(value)?));
+        }
+    }
+
+    pub fn get(self) -> Result<N, E> {
+        self.0
+    }
+}
+
+/// Why a part has no answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// See [`Overflow`], a wider implementation may still have the answer.
+    Overflow,
+    /// The input parsed but has no answer, for the reason given.
+    NoAnswer(String),
+}
+
+impl Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Overflow => Display::fmt(&Overflow, f),
+            SolveError::NoAnswer(reason) => write!(f, "no answer: {}", reason),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+impl From<Overflow> for SolveError {
+    fn from(_: Overflow) -> Self {
+        SolveError::Overflow
+    }
+}
+
+/// What a part returns: a number, or the reason there is none.
+pub trait Answer {
+    fn answer(&self) -> Result<String, SolveError>;
+}
+
+macro_rules! answer {
+    ($($answer:ty),*) => {$(
+        impl Answer for $answer {
+            fn answer(&self) -> Result<String, SolveError> {
+                Ok(self.to_string())
+            }
+        }
+    )*};
+}
+
+answer!(u32, u64, u128, usize, i32, i64, i128, String);
+
+impl<T: Answer, E: Clone + Into<SolveError>> Answer for Result<T, E> {
+    fn answer(&self) -> Result<String, SolveError> {
+        self.as_ref().map_err(|err| err.clone().into())?.answer()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checked() {
+        assert_eq!(u32::MAX.try_add(1), Err(Overflow));
+        assert_eq!(u64::of(7u8).and_then(|n| n.try_mul(6)), Ok(42));
+        assert_eq!(i32::of(u64::MAX), Err(Overflow));
+        assert_eq!(
+            u64::try_sum([Ok(1), Ok(2), Err(Overflow), Ok(3)]),
+            Err(Overflow)
+        );
+        assert_eq!(Ok::<u64, Overflow>(0).answer(), Ok("0".to_string()));
+        assert_eq!(
+            Err::<u64, Overflow>(Overflow).answer(),
+            Err(SolveError::Overflow)
+        );
+    }
+
+    #[test]
+    fn test_lcm() {
+        assert_eq!(lcm(4u64, 6), Ok(12));
+        let big = 1u64 << 40;
+        assert_eq!(lcm(big, big), Ok(big));
+        assert_eq!(lcm(big, big + 1), Err(Overflow));
+        assert_eq!(
+            lcm(u128::from(big), u128::from(big) + 1),
+            Ok((1 << 80) + (1 << 40))
+        );
+    }
+
+    #[test]
+    fn test_total() {
+        let mut total = Total::<u32>::default();
+        total.add(Ok(u32::MAX));
+        assert_eq!(total.get(), Ok(u32::MAX));
+        total.add(Ok(1));
+        total.add(Ok(0));
+        assert_eq!(total.get(), Err(Overflow));
+    }
+}
@@ -0,0 +1,130 @@
+//! Building blocks for nom parsers. Failures carry `context` labels, which
+//! [`ParseError::from_nom`] turns into the expectation shown to the user.
+
+use std::str::FromStr;
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, digit1, line_ending, multispace0, space0, space1},
+    combinator::{eof, map_res, opt, recognize},
+    error::{context, VerboseError},
+    multi::separated_list1,
+    sequence::{pair, preceded, terminated, tuple},
+};
+
+use crate::ParseError;
+
+pub type IResult<'a, O> = nom::IResult<&'a str, O, VerboseError<&'a str>>;
+
+/// Digits, read into any unsigned integer type.
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<'_, T> {
+    context("a number", map_res(digit1, str::parse))(input)
+}
+
+/// Digits with an optional leading `-`, read into any integer type.
+pub fn signed<T: FromStr>(input: &str) -> IResult<'_, T> {
+    context(
+        "a number",
+        map_res(recognize(pair(opt(char('-')), digit1)), str::parse),
+    )(input)
+}
+
+/// One or more numbers on a line, separated by spaces or tabs.
+pub fn numbers<T: FromStr>(input: &str) -> IResult<'_, Vec<T>> {
+    separated_list1(space1, signed)(input)
+}
+
+/// `header` followed by `body`, either on the same line after some spaces or
+/// starting on the next line. The header doubles as the error context, e.g.
+/// `section("seeds:", numbers)`.
+pub fn section<'a, O>(
+    header: &'static str,
+    body: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> impl FnMut(&'a str) -> IResult<'a, O> {
+    preceded(
+        tuple((context(header, tag(header)), space0, opt(line_ending))),
+        body,
+    )
+}
+
+/// One or more `line`s, one per line.
+pub fn lines<'a, O>(
+    line: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    separated_list1(line_ending, line)
+}
+
+/// One or more `block`s separated by a blank line.
+pub fn blocks<'a, O>(
+    block: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<O>> {
+    separated_list1(pair(line_ending, line_ending), block)
+}
+
+/// Runs `parser` over the whole of `input`, only trailing whitespace may be
+/// left over.
+pub fn parse_all<'a, O>(
+    day: u8,
+    input: &'a str,
+    parser: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> Result<O, ParseError> {
+    terminated(parser, pair(multispace0, context("end of input", eof)))(input)
+        .map(|(_, output)| output)
+        .map_err(|err| ParseError::from_nom(day, input, err))
+}
+
+/// Runs `parser` over every line of `input`, each line must be consumed
+/// completely.
+pub fn parse_lines<'a, O>(
+    day: u8,
+    input: &'a str,
+    mut parser: impl FnMut(&'a str) -> IResult<'a, O>,
+) -> Result<Vec<O>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            terminated(&mut parser, context("end of line", eof))(line)
+                .map(|(_, output)| output)
+                .map_err(|err| ParseError::from_nom(day, input, err))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(unsigned::<u8>("255,"), Ok((",", 255)));
+        assert!(unsigned::<u8>("256").is_err());
+        assert!(unsigned::<u32>("-1").is_err());
+        assert_eq!(signed::<i64>("-12 3"), Ok((" 3", -12)));
+        assert_eq!(numbers::<i32>("1  -2\t3\n4"), Ok(("\n4", vec![1, -2, 3])));
+        let seeds = parse_all(0, "seeds: 79 14\n", section("seeds:", numbers::<u64>));
+        assert_eq!(seeds, Ok(vec![79, 14]));
+        let map = parse_all(0, "map:\n1 2\n3 4\n", section("map:", lines(numbers::<u8>)));
+        assert_eq!(map, Ok(vec![vec![1, 2], vec![3, 4]]));
+        let groups = parse_all(0, "1\n2\n\n3\n", blocks(lines(unsigned::<u8>)));
+        assert_eq!(groups, Ok(vec![vec![1, 2], vec![3]]));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_all(0, "seeds: 1\nsoil: 2", section("seeds:", numbers::<u8>)).unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (2, 1, "end of input")
+        );
+        let err = parse_all(0, "seed: 1", section("seeds:", numbers::<u8>)).unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 1, "seeds:")
+        );
+        let err = parse_lines(0, "1 2\n3 x\n", numbers::<u8>).unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (2, 2, "end of line")
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use std::{any::Any, fmt, fmt::Display, io::BufRead, str::FromStr};
+
+use crate::{
+    generate::Rng,
+    num::{Answer, SolveError},
+    stream::{self, StreamError, Streaming},
+    ParseError,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("no part {}", s)),
+        }
+    }
+}
+
+/// A puzzle solution: the input is parsed once and both parts are solved from
+/// the parsed form.
+pub trait Solution {
+    const DAY: u8;
+    type Input: Send + Sync + 'static;
+    type Answer1: Answer;
+    type Answer2: Answer;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    fn part2(input: &Self::Input) -> Self::Answer2;
+
+    /// Other implementations of either part, e.g. a slower reference solver
+    /// kept around to compare against.
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        Vec::new()
+    }
+
+    /// A random valid input of about `size` records, lines or blocks, see
+    /// [`Day::generate`]. `None` for days without a generator.
+    fn generate(_rng: &mut Rng, _size: usize) -> Option<String> {
+        None
+    }
+}
+
+pub struct Alternative<I> {
+    pub part: Part,
+    pub name: &'static str,
+    pub solve: fn(&I) -> Result<String, SolveError>,
+}
+
+type Parsed = Box<dyn Any + Send + Sync>;
+type Parser = Box<dyn Fn(&str) -> Result<Parsed, ParseError> + Send + Sync>;
+type Solver = Box<dyn Fn(&(dyn Any + Send + Sync)) -> Result<String, SolveError> + Send + Sync>;
+type Streamer =
+    Box<dyn Fn(&mut dyn BufRead) -> Result<(String, String), StreamError> + Send + Sync>;
+
+/// A type erased [`Solution`], so that days with different input and answer
+/// types can be stored side by side.
+pub struct Day {
+    pub day: u8,
+    parse: Parser,
+    pub implementations: Vec<Implementation>,
+    stream: Option<Streamer>,
+    generate: fn(&mut Rng, usize) -> Option<String>,
+}
+
+pub struct Implementation {
+    pub part: Part,
+    pub name: &'static str,
+    /// False for the `part1`/`part2` of the [`Solution`] itself.
+    pub alternative: bool,
+    solve: Solver,
+}
+
+pub struct Input(Parsed);
+
+impl Day {
+    pub fn of<S: Solution>() -> Day {
+        let mut implementations = vec![
+            Implementation {
+                part: Part::One,
+                name: "part1",
+                alternative: false,
+                solve: Box::new(|input| S::part1(downcast::<S>(input)).answer()),
+            },
+            Implementation {
+                part: Part::Two,
+                name: "part2",
+                alternative: false,
+                solve: Box::new(|input| S::part2(downcast::<S>(input)).answer()),
+            },
+        ];
+        implementations.extend(S::alternatives().into_iter().map(|alternative| {
+            let solve = alternative.solve;
+            Implementation {
+                part: alternative.part,
+                name: alternative.name,
+                alternative: true,
+                solve: Box::new(move |input| solve(downcast::<S>(input))),
+            }
+        }));
+        Day {
+            day: S::DAY,
+            parse: Box::new(|input| Ok(Box::new(S::parse(input)?) as Parsed)),
+            implementations,
+            stream: None,
+            generate: S::generate,
+        }
+    }
+
+    /// Like [`Day::of`], for a day that can also be solved with
+    /// [`Day::stream`].
+    pub fn streaming<S: Streaming>() -> Day {
+        Day {
+            stream: Some(Box::new(|reader| {
+                let (part1, part2) = stream::solve::<S>(reader)?;
+                Ok((part1.answer()?, part2.answer()?))
+            })),
+            ..Day::of::<S>()
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Input, ParseError> {
+        (self.parse)(input).map(Input)
+    }
+
+    pub fn can_stream(&self) -> bool {
+        self.stream.is_some()
+    }
+
+    /// Both answers, read from `reader` one record at a time. `None` if the
+    /// day cannot be streamed.
+    pub fn stream(
+        &self,
+        reader: &mut dyn BufRead,
+    ) -> Option<Result<(String, String), StreamError>> {
+        self.stream.as_ref().map(|stream| stream(reader))
+    }
+
+    /// An input of about `size` records, always the same for the same `seed`.
+    /// `None` if the day has no generator.
+    pub fn generate(&self, seed: u64, size: usize) -> Option<String> {
+        (self.generate)(&mut Rng::new(seed), size)
+    }
+
+    pub fn implementation(&self, name: &str) -> Option<&Implementation> {
+        self.implementations.iter().find(|imp| imp.name == name)
+    }
+
+    pub fn part(&self, part: Part) -> impl Iterator<Item = &Implementation> {
+        self.implementations
+            .iter()
+            .filter(move |imp| imp.part == part)
+    }
+}
+
+impl Implementation {
+    /// The answer, or why there is none.
+    pub fn solve(&self, input: &Input) -> Result<String, SolveError> {
+        (self.solve)(input.0.as_ref())
+    }
+}
+
+fn downcast<S: Solution>(input: &(dyn Any + Send + Sync)) -> &S::Input {
+    input
+        .downcast_ref::<S::Input>()
+        .expect("Input parsed by a different day")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 0;
+        type Input = Vec<u32>;
+        type Answer1 = u32;
+        type Answer2 = usize;
+
+        fn parse(input: &str) -> Result<Self::Input, ParseError> {
+            input
+                .lines()
+                .map(|line| {
+                    line.parse()
+                        .map_err(|_| ParseError::at(0, input, line, "a number"))
+                })
+                .collect()
+        }
+
+        fn part1(input: &Self::Input) -> u32 {
+            input.iter().sum()
+        }
+
+        fn part2(input: &Self::Input) -> usize {
+            input.len()
+        }
+
+        fn alternatives() -> Vec<Alternative<Self::Input>> {
+            vec![Alternative {
+                part: Part::One,
+                name: "part1_fold",
+                solve: |input| {
+                    input
+                        .iter()
+                        .copied()
+                        .reduce(|acc, x| acc + x)
+                        .unwrap_or(0)
+                        .answer()
+                },
+            }]
+        }
+    }
+
+    #[test]
+    fn test_erased_day() {
+        let day = Day::of::<Sum>();
+        let input = day.parse("1\n2\n3\n").unwrap();
+        assert_eq!(
+            day.implementation("part1").unwrap().solve(&input),
+            Ok("6".into())
+        );
+        assert_eq!(
+            day.implementation("part2").unwrap().solve(&input),
+            Ok("3".into())
+        );
+        assert_eq!(day.part(Part::One).count(), 2);
+        assert_eq!(
+            day.implementation("part1_fold").unwrap().solve(&input),
+            Ok("6".into())
+        );
+        assert_eq!(day.parse("1\nx\n").err().unwrap().line, 2);
+    }
+}
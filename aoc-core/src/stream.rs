@@ -0,0 +1,337 @@
+//! Solving a day while reading its input one record at a time, so that the
+//! input never has to be held in memory as a whole.
+
+use std::{
+    error::Error,
+    fmt,
+    io::{self, BufRead},
+};
+
+use crate::{num::SolveError, ParseError, Solution};
+
+/// How the records of an input are delimited.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Separator {
+    /// One record per line.
+    Line,
+    /// Records of one or more lines, separated by blank lines.
+    Block,
+    /// Records separated by a byte, such as the commas of day 15.
+    Byte(u8),
+}
+
+/// A [`Solution`] that can also be solved from a stream of records. When both
+/// answers are sums over the records, only the totals are kept.
+pub trait Streaming: Solution {
+    const SEPARATOR: Separator = Separator::Line;
+    /// Whatever has to be kept between records.
+    type State: Default;
+
+    /// Takes in one record, without its separator. Errors are positioned
+    /// within `record`. Line records are never empty, [`solve`] rejects
+    /// blank lines.
+    fn feed(state: &mut Self::State, record: &str) -> Result<(), ParseError>;
+    fn finish(state: Self::State) -> Result<(Self::Answer1, Self::Answer2), ParseError>;
+}
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+    Solve(SolveError),
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StreamError::Io(err) => write!(f, "cannot read input: {}", err),
+            StreamError::Parse(err) => err.fmt(f),
+            StreamError::Solve(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for StreamError {}
+
+impl From<io::Error> for StreamError {
+    fn from(err: io::Error) -> Self {
+        StreamError::Io(err)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(err: ParseError) -> Self {
+        StreamError::Parse(err)
+    }
+}
+
+impl From<SolveError> for StreamError {
+    fn from(err: SolveError) -> Self {
+        StreamError::Solve(err)
+    }
+}
+
+/// One record and where it starts in the input, both 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    pub text: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The records of `reader`. Line endings, `\r\n` included, are dropped from
+/// line records and kept inside block records.
+pub fn records<R: BufRead>(reader: R, separator: Separator) -> Records<R> {
+    Records {
+        reader,
+        separator,
+        line: 1,
+        column: 1,
+        separated: false,
+        line_text: String::new(),
+        prefix: String::new(),
+    }
+}
+
+pub struct Records<R> {
+    reader: R,
+    separator: Separator,
+    line: usize,
+    column: usize,
+    /// Whether the last byte record ended in a separator, so that another
+    /// one, if empty, follows.
+    separated: bool,
+    /// The current line up to the end of the last byte record.
+    line_text: String,
+    /// The line of the last byte record up to where the record starts.
+    prefix: String,
+}
+
+impl<R: BufRead> Records<R> {
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        self.line += 1;
+        Ok(Some(line))
+    }
+
+    fn next_line(&mut self) -> io::Result<Option<Record>> {
+        let line = self.line;
+        Ok(self.read_line()?.map(|text| Record {
+            text: text
+                .strip_suffix('\n')
+                .map(|text| text.strip_suffix('\r').unwrap_or(text))
+                .unwrap_or(&text)
+                .to_string(),
+            line,
+            column: 1,
+        }))
+    }
+
+    fn next_block(&mut self) -> io::Result<Option<Record>> {
+        let mut block = Record {
+            text: String::new(),
+            line: self.line,
+            column: 1,
+        };
+        while let Some(line) = self.read_line()? {
+            if !line.trim_end().is_empty() {
+                block.text.push_str(&line);
+            } else if block.text.is_empty() {
+                block.line = self.line;
+            } else {
+                break;
+            }
+        }
+        Ok((!block.text.is_empty()).then_some(block))
+    }
+
+    fn next_separated(&mut self, separator: u8) -> io::Result<Option<Record>> {
+        let mut bytes = vec![];
+        if self.reader.read_until(separator, &mut bytes)? == 0 && !self.separated {
+            return Ok(None);
+        }
+        self.separated = bytes.last() == Some(&separator);
+        let text = String::from_utf8(bytes)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        let record = Record {
+            text: text
+                .strip_suffix(separator as char)
+                .unwrap_or(&text)
+                .to_string(),
+            line: self.line,
+            column: self.column,
+        };
+        self.prefix.clone_from(&self.line_text);
+        match text.rfind('\n') {
+            Some(indx) => {
+                self.line += text.matches('\n').count();
+                self.column = text[indx + 1..].chars().count() + 1;
+                self.line_text = text[indx + 1..].to_string();
+            }
+            None => {
+                self.column += text.chars().count();
+                self.line_text.push_str(&text);
+            }
+        }
+        Ok(Some(record))
+    }
+
+    /// Moves `err`, positioned within `record`, to where the record is in
+    /// the input. A byte record shares its lines with others: the column is
+    /// shifted on its first line, and the snippet extended to the whole
+    /// line, reading the rest of its last line from the input.
+    fn locate(&mut self, record: &Record, mut err: ParseError) -> io::Result<ParseError> {
+        if let Separator::Byte(separator) = self.separator {
+            if err.line == 1 {
+                err.column += record.column - 1;
+                err.snippet.insert_str(0, &self.prefix);
+            }
+            if err.line == record.text.matches('\n').count() + 1 && self.separated {
+                let mut rest = String::new();
+                self.reader.read_line(&mut rest)?;
+                err.snippet.push(separator as char);
+                err.snippet
+                    .push_str(rest.trim_end_matches('\n').trim_end_matches('\r'));
+            }
+        }
+        err.line += record.line - 1;
+        Ok(err)
+    }
+}
+
+impl<R: BufRead> Iterator for Records<R> {
+    type Item = io::Result<Record>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.separator {
+            Separator::Line => self.next_line(),
+            Separator::Block => self.next_block(),
+            Separator::Byte(separator) => self.next_separated(separator),
+        }
+        .transpose()
+    }
+}
+
+/// Solves both parts of `S` from `reader`. Parse errors are moved to where
+/// their record is in the input, as the parser of the whole input would
+/// report them. A blank line is an error when records are lines, as it is
+/// for the parsers of whole inputs.
+pub fn solve<S: Streaming>(reader: impl BufRead) -> Result<(S::Answer1, S::Answer2), StreamError> {
+    let mut state = S::State::default();
+    let mut records = records(reader, S::SEPARATOR);
+    while let Some(record) = records.next() {
+        let record = record?;
+        let fed = if S::SEPARATOR == Separator::Line && record.text.is_empty() {
+            Err(ParseError::new(S::DAY, "", 0, "a line, not a blank one"))
+        } else {
+            S::feed(&mut state, &record.text)
+        };
+        if let Err(err) = fed {
+            return Err(records.locate(&record, err)?.into());
+        }
+    }
+    Ok(S::finish(state).map_err(|mut err| {
+        err.line += records.line - 1;
+        err
+    })?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read(input: &str, separator: Separator) -> Vec<(String, usize, usize)> {
+        records(input.as_bytes(), separator)
+            .map(|record| {
+                let record = record.unwrap();
+                (record.text, record.line, record.column)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_records() {
+        let owned = |records: &[(&str, usize, usize)]| {
+            records
+                .iter()
+                .map(|&(text, line, column)| (text.to_string(), line, column))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            read("1 2\r\n\n3\n", Separator::Line),
+            owned(&[("1 2", 1, 1), ("", 2, 1), ("3", 3, 1)])
+        );
+        assert_eq!(
+            read("\n#.\n.#\n\n\n##\n", Separator::Block),
+            owned(&[("#.\n.#\n", 2, 1), ("##\n", 6, 1)])
+        );
+        assert_eq!(
+            read("rn=1,cm-\n,qp,", Separator::Byte(b',')),
+            owned(&[("rn=1", 1, 1), ("cm-\n", 1, 6), ("qp", 2, 2), ("", 2, 5)])
+        );
+    }
+
+    /// Sums the numbers and counts them, which needs at least one line.
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 0;
+        type Input = ();
+        type Answer1 = u32;
+        type Answer2 = usize;
+
+        fn parse(_: &str) -> Result<(), ParseError> {
+            Ok(())
+        }
+
+        fn part1(_: &()) -> u32 {
+            0
+        }
+
+        fn part2(_: &()) -> usize {
+            0
+        }
+    }
+
+    impl Streaming for Sum {
+        type State = (u32, usize);
+
+        fn feed(state: &mut (u32, usize), record: &str) -> Result<(), ParseError> {
+            let number = record
+                .parse::<u32>()
+                .map_err(|_| ParseError::new(0, record, 0, "a number"))?;
+            *state = (state.0 + number, state.1 + 1);
+            Ok(())
+        }
+
+        fn finish(state: (u32, usize)) -> Result<(u32, usize), ParseError> {
+            match state.1 {
+                0 => Err(ParseError::new(0, "", 0, "a number")),
+                _ => Ok(state),
+            }
+        }
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(solve::<Sum>("1\n2\n3\n".as_bytes()).unwrap(), (6, 3));
+        let Err(StreamError::Parse(err)) = solve::<Sum>("1\n2\nx\n".as_bytes()) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((err.line, err.column, err.snippet.as_str()), (3, 1, "x"));
+        let Err(StreamError::Parse(err)) = solve::<Sum>("1\n\n3\n".as_bytes()) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(
+            (err.line, err.expected.as_str()),
+            (2, "a line, not a blank one")
+        );
+        let Err(StreamError::Parse(err)) = solve::<Sum>("".as_bytes()) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(err.line, 1);
+    }
+}
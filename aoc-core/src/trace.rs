@@ -0,0 +1,132 @@
+//! Typed events from inside the solvers, for debugging. Events are only built
+//! while a [`Subscriber`] is installed with [`with`] on the current thread,
+//! otherwise [`emit`] costs one atomic load.
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::BTreeMap,
+    fmt::Debug,
+    io::Write,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+pub trait Event: Debug {
+    /// The kind of event, e.g. the name of the enum variant.
+    fn name(&self) -> &'static str;
+}
+
+pub trait Subscriber: Any {
+    fn event(&mut self, event: &dyn Event);
+}
+
+/// Number of threads with a subscriber installed.
+static ACTIVE: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static SUBSCRIBER: RefCell<Option<Box<dyn Subscriber>>> = const { RefCell::new(None) };
+}
+
+/// Sends the event built by `event` to the subscriber of this thread, if any.
+#[inline]
+pub fn emit<E: Event>(event: impl FnOnce() -> E) {
+    if ACTIVE.load(Ordering::Relaxed) == 0 {
+        return;
+    }
+    SUBSCRIBER.with(|subscriber| {
+        if let Some(subscriber) = subscriber.borrow_mut().as_mut() {
+            subscriber.event(&event());
+        }
+    });
+}
+
+/// Runs `f` with `subscriber` receiving the events emitted on this thread,
+/// then hands the subscriber back.
+pub fn with<S: Subscriber, T>(subscriber: S, f: impl FnOnce() -> T) -> (T, S) {
+    /// Uninstalls the subscriber even if `f` panics.
+    struct Installed(Option<Box<dyn Subscriber>>);
+
+    impl Drop for Installed {
+        fn drop(&mut self) {
+            SUBSCRIBER.with(|subscriber| subscriber.replace(self.0.take()));
+            ACTIVE.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+
+    let previous = SUBSCRIBER.with(|installed| installed.replace(Some(Box::new(subscriber))));
+    ACTIVE.fetch_add(1, Ordering::Relaxed);
+    let installed = Installed(previous);
+    let output = f();
+    let subscriber = SUBSCRIBER.with(|subscriber| subscriber.take()).unwrap();
+    drop(installed);
+    let subscriber = (subscriber as Box<dyn Any>)
+        .downcast::<S>()
+        .expect("subscriber replaced while in use");
+    (output, *subscriber)
+}
+
+/// Writes every event on a line of its own.
+pub struct Print<W>(pub W);
+
+impl<W: Write + 'static> Subscriber for Print<W> {
+    fn event(&mut self, event: &dyn Event) {
+        // Tracing is best effort, a closed pipe must not stop the solver.
+        let _ = writeln!(self.0, "{:?}", event);
+    }
+}
+
+/// Counts the events of each kind.
+#[derive(Debug, Default)]
+pub struct Count(pub BTreeMap<&'static str, usize>);
+
+impl Subscriber for Count {
+    fn event(&mut self, event: &dyn Event) {
+        *self.0.entry(event.name()).or_default() += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    enum Probe {
+        Hit,
+        Miss,
+    }
+
+    impl Event for Probe {
+        fn name(&self) -> &'static str {
+            match self {
+                Probe::Hit => "hit",
+                Probe::Miss => "miss",
+            }
+        }
+    }
+
+    fn probe() -> usize {
+        let mut built = 0;
+        for key in [1, 2, 1] {
+            emit(|| {
+                built += 1;
+                if key == 1 {
+                    Probe::Hit
+                } else {
+                    Probe::Miss
+                }
+            });
+        }
+        built
+    }
+
+    #[test]
+    fn test_subscribers() {
+        assert_eq!(probe(), 0);
+        let (built, count) = with(Count::default(), probe);
+        assert_eq!(built, 3);
+        assert_eq!(count.0, BTreeMap::from([("hit", 2), ("miss", 1)]));
+        let (_, print) = with(Print(Vec::new()), probe);
+        assert_eq!(String::from_utf8(print.0).unwrap(), "Hit\nMiss\nHit\n");
+        assert_eq!(probe(), 0);
+    }
+}
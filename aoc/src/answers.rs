@@ -0,0 +1,117 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use aoc_core::Part;
+
+/// The accepted answers for a day's `input.txt`, kept in an `answers.toml`
+/// holding `part1 = "..."` and `part2 = "..."`.
+pub struct Answers {
+    path: PathBuf,
+    parts: BTreeMap<Part, String>,
+}
+
+impl Answers {
+    /// A missing file means that nothing has been recorded yet.
+    pub fn load(path: &Path) -> Result<Answers, String> {
+        let parts = match fs::read_to_string(path) {
+            Ok(text) => parse(&text).map_err(|err| format!("{}: {}", path.display(), err))?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(format!("cannot read {}: {}", path.display(), err)),
+        };
+        Ok(Answers {
+            path: path.to_path_buf(),
+            parts,
+        })
+    }
+
+    pub fn get(&self, part: Part) -> Option<&str> {
+        self.parts.get(&part).map(String::as_str)
+    }
+
+    /// Stores `answer` for `part` and writes the file back.
+    pub fn record(&mut self, part: Part, answer: &str) -> Result<(), String> {
+        if answer.contains(['"', '\\', '\n']) {
+            return Err(format!("cannot record answer {:?}", answer));
+        }
+        self.parts.insert(part, answer.to_string());
+        fs::write(&self.path, format(&self.parts))
+            .map_err(|err| format!("cannot write {}: {}", self.path.display(), err))
+    }
+}
+
+fn parse(text: &str) -> Result<BTreeMap<Part, String>, String> {
+    let mut parts = BTreeMap::new();
+    for (indx, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let err = |message: &str| format!("line {}: {}", indx + 1, message);
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| err("expected `partN = \"answer\"`"))?;
+        let part = key
+            .trim()
+            .strip_prefix("part")
+            .and_then(|part| part.parse::<Part>().ok())
+            .ok_or_else(|| err("expected `part1` or `part2`"))?;
+        let value = value.trim();
+        let answer = match value.strip_prefix('"') {
+            Some(quoted) => quoted
+                .strip_suffix('"')
+                .filter(|answer| !answer.contains(['"', '\\']))
+                .ok_or_else(|| err("expected a quoted answer"))?,
+            None if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit() || c == '-') => {
+                value
+            }
+            None => return Err(err("expected a quoted answer")),
+        };
+        if parts.insert(part, answer.to_string()).is_some() {
+            return Err(err(&format!("part{} is given twice", part)));
+        }
+    }
+    Ok(parts)
+}
+
+fn format(parts: &BTreeMap<Part, String>) -> String {
+    parts
+        .iter()
+        .map(|(part, answer)| format!("part{} = \"{}\"\n", part, answer))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let parts = parse("# day 8\npart1 = \"13301\"\n\npart2 = 7309459565207\n").unwrap();
+        assert_eq!(parts[&Part::One], "13301");
+        assert_eq!(parts[&Part::Two], "7309459565207");
+        assert_eq!(
+            format(&parts),
+            "part1 = \"13301\"\npart2 = \"7309459565207\"\n"
+        );
+        assert_eq!(parse(&format(&parts)).unwrap(), parts);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert_eq!(
+            parse("part1 = \"1\"\npart3 = \"2\"\n").unwrap_err(),
+            "line 2: expected `part1` or `part2`"
+        );
+        assert_eq!(
+            parse("part1 = \"1\npart1 = \"2\"").unwrap_err(),
+            "line 1: expected a quoted answer"
+        );
+        assert_eq!(
+            parse("part2 = 1\npart2 = 1").unwrap_err(),
+            "line 2: part2 is given twice"
+        );
+    }
+}
@@ -0,0 +1,78 @@
+use std::{collections::BTreeMap, str::FromStr};
+
+/// Command line of the form `aoc <command> [--name value | --switch]...`.
+#[derive(Debug)]
+pub struct Args {
+    pub command: Option<String>,
+    options: BTreeMap<String, Option<String>>,
+}
+
+impl Args {
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+        let mut args = args.into_iter().peekable();
+        let command = args.next_if(|arg| !arg.starts_with("--"));
+        let mut options = BTreeMap::new();
+        while let Some(arg) = args.next() {
+            let Some(name) = arg.strip_prefix("--") else {
+                return Err(format!("unexpected argument `{}`", arg));
+            };
+            let (name, value) = match name.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (
+                    name.to_string(),
+                    args.next_if(|next| !next.starts_with("--")),
+                ),
+            };
+            options.insert(name, value);
+        }
+        Ok(Args { command, options })
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.options.get(name).and_then(|value| value.as_deref())
+    }
+
+    pub fn get<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.value(name)
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|_| format!("invalid value `{}` for --{}", value, name))
+            })
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(line: &str) -> Args {
+        Args::parse(line.split(' ').map(|arg| arg.to_string())).unwrap()
+    }
+
+    #[test]
+    fn test_options_and_switches() {
+        let args = parse("run --day 7 --part=2 --all --input -");
+        assert_eq!(args.command.as_deref(), Some("run"));
+        assert_eq!(args.get::<u8>("day").unwrap(), Some(7));
+        assert_eq!(args.get::<u8>("part").unwrap(), Some(2));
+        assert!(args.flag("all"));
+        assert_eq!(args.value("all"), None);
+        assert_eq!(args.value("input"), Some("-"));
+        let args = parse("--help");
+        assert_eq!(args.command, None);
+        assert!(args.flag("help"));
+    }
+
+    #[test]
+    fn test_invalid_value() {
+        let args = parse("run --day seven");
+        assert!(args.get::<u8>("day").is_err());
+        assert!(Args::parse(["run".to_string(), "7".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,83 @@
+use std::time::{Duration, Instant};
+
+/// Summary of the timings of repeated runs of the same code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub iterations: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub mean: Duration,
+}
+
+impl Stats {
+    /// Panics if `samples` is empty.
+    pub fn of(mut samples: Vec<Duration>) -> Stats {
+        assert!(!samples.is_empty(), "no samples");
+        samples.sort();
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+        // Nearest rank: the smallest sample with at least 95% of the samples
+        // at or below it.
+        let p95 = samples[(n * 95).div_ceil(100) - 1];
+        Stats {
+            iterations: n,
+            min: samples[0],
+            median,
+            p95,
+            mean: samples.iter().sum::<Duration>() / n as u32,
+        }
+    }
+}
+
+/// Runs `f` `warmup` times untimed, then `iterations` times timed. Returns the
+/// result of the last run alongside the timings.
+pub fn measure<T>(warmup: usize, iterations: usize, mut f: impl FnMut() -> T) -> (T, Stats) {
+    for _ in 0..warmup {
+        drop(f());
+    }
+    let mut samples = Vec::with_capacity(iterations);
+    let mut result = None;
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        let value = f();
+        samples.push(start.elapsed());
+        // Dropped outside of the timed region, a large parsed input can take
+        // a while to free.
+        drop(result.replace(value));
+    }
+    (result.unwrap(), Stats::of(samples))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = (1..=20).rev().map(Duration::from_millis).collect();
+        let stats = Stats::of(samples);
+        assert_eq!(stats.iterations, 20);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_micros(10_500));
+        assert_eq!(stats.p95, Duration::from_millis(19));
+        assert_eq!(stats.mean, Duration::from_micros(10_500));
+
+        let stats = Stats::of(vec![Duration::from_millis(3)]);
+        assert_eq!((stats.median, stats.p95), (stats.min, stats.min));
+    }
+
+    #[test]
+    fn test_measure() {
+        let mut calls = 0;
+        let (last, stats) = measure(2, 5, || {
+            calls += 1;
+            calls
+        });
+        assert_eq!((last, calls, stats.iterations), (7, 7, 5));
+    }
+}
@@ -0,0 +1,211 @@
+use std::{fs, path::Path};
+
+use aoc_core::Part;
+
+/// An example input found in a puzzle description, with the answer the
+/// description gives for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub part: Part,
+    pub input: String,
+    pub answer: String,
+}
+
+/// Finds the examples in the HTML of a puzzle page. Each `<article>` is one
+/// part. An answer is an emphasized `<code><em>` and belongs to the closest
+/// `<pre><code>` block before it that `is_input` accepts, later answers for the
+/// same block replacing earlier ones. Part 2 may reuse the last input of part
+/// 1.
+pub fn extract(html: &str, is_input: impl Fn(&str) -> bool) -> Vec<Example> {
+    let mut examples: Vec<Example> = vec![];
+    let mut input: Option<String> = None;
+    for (article, part) in articles(html).into_iter().zip([Part::One, Part::Two]) {
+        let mut rest = article;
+        loop {
+            let block = rest.find("<pre><code>");
+            let answer = rest.find("<code><em>");
+            match (block, answer) {
+                (Some(block), answer) if answer.is_none_or(|answer| block < answer) => {
+                    let (text, after) = element(&rest[block..], "</code>");
+                    if is_input(&text) {
+                        input = Some(text);
+                    }
+                    rest = after;
+                }
+                (_, Some(answer)) => {
+                    let (answer, after) = element(&rest[answer..], "</em>");
+                    rest = after;
+                    let Some(input) = &input else {
+                        continue;
+                    };
+                    match examples.last_mut() {
+                        Some(last) if last.part == part && &last.input == input => {
+                            last.answer = answer;
+                        }
+                        _ => examples.push(Example {
+                            part,
+                            input: input.clone(),
+                            answer,
+                        }),
+                    }
+                }
+                _ => break,
+            }
+        }
+    }
+    examples
+}
+
+/// The `<article>` elements, or the whole page if there are none.
+fn articles(html: &str) -> Vec<&str> {
+    let articles = html
+        .split("<article")
+        .skip(1)
+        .map(|article| article.split("</article>").next().unwrap())
+        .collect::<Vec<&str>>();
+    if articles.is_empty() {
+        vec![html]
+    } else {
+        articles
+    }
+}
+
+/// The text of the element at the start of `html` up to the closing `end`
+/// tag, and what follows it.
+fn element<'a>(html: &'a str, end: &str) -> (String, &'a str) {
+    let (inner, rest) = html.split_once(end).unwrap_or((html, ""));
+    (unescape(&strip_tags(inner)), rest)
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    text
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Writes each distinct example input to `dir` as `input.dev.exampleN.txt`
+/// and a `tests/examples.rs` checking the answers of `day`. Returns the
+/// written paths.
+pub fn write(dir: &Path, day: u8, examples: &[Example]) -> Result<Vec<String>, String> {
+    let write = |path: &Path, contents: &str| {
+        fs::write(path, contents).map_err(|err| format!("cannot write {}: {}", path.display(), err))
+    };
+    let mut inputs: Vec<&str> = vec![];
+    let mut written = vec![];
+    let mut table = String::new();
+    for example in examples {
+        let indx = match inputs.iter().position(|&input| input == example.input) {
+            Some(indx) => indx,
+            None => {
+                inputs.push(&example.input);
+                let file = format!("input.dev.example{}.txt", inputs.len());
+                write(&dir.join(&file), &example.input)?;
+                written.push(file);
+                inputs.len() - 1
+            }
+        };
+        table.push_str(&format!(
+            "    Example {{\n        part: Part::{},\n        file: \"input.dev.example{}.txt\",\n        answer: {:?},\n    }},\n",
+            if example.part == Part::One { "One" } else { "Two" },
+            indx + 1,
+            example.answer
+        ));
+    }
+    let tests = dir.join("tests");
+    fs::create_dir_all(&tests)
+        .map_err(|err| format!("cannot create {}: {}", tests.display(), err))?;
+    // An empty table, as written by `aoc new`, has no use for `Part`.
+    let part = if examples.is_empty() { "" } else { ", Part" };
+    let source = format!(
+        "//! Generated by `aoc examples --day {day}` from the puzzle description.\n\
+         \n\
+         use aoc_core::{{example::assert_examples, Day, Example{part}}};\n\
+         \n\
+         const EXAMPLES: &[Example] = &[\n{table}];\n\
+         \n\
+         #[test]\n\
+         fn test_examples() {{\n    \
+             assert_examples(&Day::of::<day{day}::Day{day}>(), EXAMPLES);\n\
+         }}\n"
+    );
+    write(&tests.join("examples.rs"), &source)?;
+    written.push("tests/examples.rs".to_string());
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = "<main>\
+        <article class=\"day-desc\"><h2>--- Day 8 ---</h2>\
+        <pre><code>RL\n\nAAA = (BBB, CCC)\n</code></pre>\
+        <p>Takes <code><em>2</em></code> steps.</p>\
+        <pre><code>LLR\n\nAAA = (BBB, BBB)\n</code></pre>\
+        <p>Starting at <code>AAA</code> takes <code><em>3</em></code>, no, \
+        <code><em>6</em></code> steps.</p>\
+        <pre><code><em>AAA</em> -&gt; BBB</code></pre></article>\
+        <p>Your answer was <code>13301</code>.</p>\
+        <article class=\"day-desc\"><h2>--- Part Two ---</h2>\
+        <p>Now it takes <code><em>1</em></code> step.</p></article></main>";
+
+    #[test]
+    fn test_extract() {
+        let examples = extract(PAGE, |text| text.contains(" = ("));
+        let part1 = |input: &str, answer: &str| Example {
+            part: Part::One,
+            input: input.to_string(),
+            answer: answer.to_string(),
+        };
+        assert_eq!(
+            examples,
+            [
+                part1("RL\n\nAAA = (BBB, CCC)\n", "2"),
+                part1("LLR\n\nAAA = (BBB, BBB)\n", "6"),
+                Example {
+                    part: Part::Two,
+                    ..part1("LLR\n\nAAA = (BBB, BBB)\n", "1")
+                },
+            ]
+        );
+        let examples = extract(PAGE, |_| true);
+        assert_eq!(examples[2].input, "AAA -> BBB");
+        assert_eq!(examples[2].answer, "1");
+    }
+
+    #[test]
+    fn test_write() {
+        let dir = std::env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let examples = extract(PAGE, |text| text.contains(" = ("));
+        let written = write(&dir, 8, &examples).unwrap();
+        assert_eq!(
+            written,
+            [
+                "input.dev.example1.txt",
+                "input.dev.example2.txt",
+                "tests/examples.rs"
+            ]
+        );
+        let source = fs::read_to_string(dir.join("tests/examples.rs")).unwrap();
+        assert!(source.contains("file: \"input.dev.example2.txt\",\n        answer: \"1\","));
+        assert!(source.contains("Day::of::<day8::Day8>()"));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,206 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u16 = 2023;
+/// Minimum time between two requests to the puzzle server.
+pub const INTERVAL: Duration = Duration::from_secs(5);
+const USER_AGENT: &str = "aoc-runner (personal puzzle input fetcher)";
+
+/// Puzzle inputs, read from `root/dayN/input.txt` and downloaded there when
+/// missing. A cached day is never fetched again.
+pub struct Provider {
+    pub root: PathBuf,
+    pub base_url: String,
+    pub year: u16,
+    /// Value of the `session` cookie, only needed when something is fetched.
+    pub session: Option<String>,
+    pub interval: Duration,
+}
+
+impl Provider {
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        self.root.join(format!("day{}", day)).join("input.txt")
+    }
+
+    /// Whether the input of `day` is cached or can be downloaded.
+    pub fn available(&self, day: u8) -> bool {
+        self.session.is_some() || self.input_path(day).exists()
+    }
+
+    pub fn input(&self, day: u8) -> Result<String, String> {
+        let path = self.input_path(day);
+        if !path.exists() {
+            self.fetch(day)?;
+        }
+        fs::read_to_string(&path).map_err(|err| format!("cannot read {}: {}", path.display(), err))
+    }
+
+    /// Downloads the input of `day` unless it is cached, returns whether a
+    /// request was made.
+    pub fn fetch(&self, day: u8) -> Result<bool, String> {
+        let path = self.input_path(day);
+        if path.exists() {
+            return Ok(false);
+        }
+        let Some(session) = &self.session else {
+            return Err(format!(
+                "{} is missing and no session cookie is set, use AOC_SESSION or {}",
+                path.display(),
+                session_file().map_or("a session file".to_string(), |path| path
+                    .display()
+                    .to_string())
+            ));
+        };
+        self.wait_turn()?;
+        let url = format!("{}/{}/day/{}/input", self.base_url, self.year, day);
+        let input = ureq::get(&url)
+            .set("Cookie", &format!("session={}", session))
+            .set("User-Agent", USER_AGENT)
+            .call()
+            .map_err(|err| format!("cannot fetch {}: {}", url, err))?
+            .into_string()
+            .map_err(|err| format!("cannot read {}: {}", url, err))?;
+        fs::create_dir_all(path.parent().unwrap())
+            .and_then(|_| fs::write(&path, input))
+            .map_err(|err| format!("cannot write {}: {}", path.display(), err))?;
+        Ok(true)
+    }
+
+    /// Sleeps until `interval` has passed since the last request, which is
+    /// remembered in `root` so that it holds across runs.
+    fn wait_turn(&self) -> Result<(), String> {
+        let stamp = self.root.join(".last-fetch");
+        let now = || SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+        let last = fs::read_to_string(&stamp)
+            .ok()
+            .and_then(|millis| millis.trim().parse().ok())
+            .map(Duration::from_millis);
+        if let Some(wait) = last.and_then(|last| (last + self.interval).checked_sub(now())) {
+            thread::sleep(wait);
+        }
+        fs::write(&stamp, now().as_millis().to_string())
+            .map_err(|err| format!("cannot write {}: {}", stamp.display(), err))
+    }
+}
+
+fn session_file() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("AOC_SESSION_FILE") {
+        return Some(PathBuf::from(path));
+    }
+    env::var_os("HOME").map(|home| Path::new(&home).join(".config/aoc/session"))
+}
+
+/// The session cookie from `AOC_SESSION`, or else from the file named by
+/// `AOC_SESSION_FILE` or `~/.config/aoc/session`.
+pub fn session() -> Option<String> {
+    env::var("AOC_SESSION")
+        .ok()
+        .or_else(|| fs::read_to_string(session_file()?).ok())
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        sync::{Arc, Mutex},
+        time::Instant,
+    };
+
+    use super::*;
+
+    /// Serves `body` for every request, or a 404 for day 25, and records the
+    /// request line and cookie of each request.
+    fn stub_server(body: &'static str) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(vec![]));
+        let log = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut line = String::new();
+                while reader.read_line(&mut line).unwrap() > 2 {
+                    if line.starts_with("GET") || line.starts_with("Cookie") {
+                        request.push_str(line.trim_end());
+                        request.push(' ');
+                    }
+                    line.clear();
+                }
+                let status = if request.contains("/day/25/") {
+                    "404 Not Found"
+                } else {
+                    "200 OK"
+                };
+                log.lock().unwrap().push(request.trim_end().to_string());
+                write!(
+                    stream,
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    fn provider(name: &str, base_url: String) -> Provider {
+        let root = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        Provider {
+            root,
+            base_url,
+            year: 2023,
+            session: Some("secret".to_string()),
+            interval: Duration::from_millis(200),
+        }
+    }
+
+    #[test]
+    fn test_fetch_once() {
+        let (url, requests) = stub_server("1abc2\n");
+        let provider = provider("once", url);
+        assert_eq!(provider.input(1), Ok("1abc2\n".to_string()));
+        assert_eq!(provider.fetch(1), Ok(false));
+        assert_eq!(provider.input(1), Ok("1abc2\n".to_string()));
+        assert_eq!(
+            *requests.lock().unwrap(),
+            ["GET /2023/day/1/input HTTP/1.1 Cookie: session=secret"]
+        );
+        assert!(provider.fetch(25).is_err());
+        assert!(!provider.input_path(25).exists());
+        fs::remove_dir_all(&provider.root).unwrap();
+    }
+
+    #[test]
+    fn test_rate_limit_and_session() {
+        let (url, requests) = stub_server("0 3 6\n");
+        let mut provider = provider("limit", url);
+        let start = Instant::now();
+        assert_eq!(provider.fetch(8), Ok(true));
+        assert_eq!(provider.fetch(9), Ok(true));
+        assert!(start.elapsed() >= provider.interval);
+        assert_eq!(requests.lock().unwrap().len(), 2);
+        provider.session = None;
+        assert!(provider.available(9));
+        assert!(!provider.available(10));
+        assert!(provider
+            .fetch(10)
+            .unwrap_err()
+            .contains("no session cookie"));
+        assert_eq!(provider.input(9), Ok("0 3 6\n".to_string()));
+        fs::remove_dir_all(&provider.root).unwrap();
+    }
+}
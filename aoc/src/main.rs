@@ -0,0 +1,681 @@
+mod answers;
+mod args;
+mod bench;
+mod examples;
+mod fetch;
+mod output;
+mod registry;
+mod scaffold;
+mod table;
+mod tracer;
+mod watch;
+
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    process, thread,
+    time::Instant,
+};
+
+use aoc_core::{num::SolveError, stream::StreamError, Day, Implementation, Part};
+
+use crate::{
+    answers::Answers,
+    args::Args,
+    bench::{measure, Stats},
+    fetch::Provider,
+    output::{json_string, Format, Printer, Run},
+    table::Table,
+    tracer::Tracer,
+};
+
+const USAGE: &str = "usage:
+    aoc run (--day N | --all) [--part 1|2] [--impl NAME | --alternatives]
+        [--input PATH | -] [--format table|json|csv|tsv]
+        [--trace print|count | --trace-file PATH] [--parallel] [--wide]
+    aoc run (--day N | --all) --stream [--part 1|2] [--input PATH | -]
+        [--format ...] [--trace ...] [--parallel]
+    aoc bench (--day N | --all) [--part 1|2] [--impl NAME | --alternatives]
+        [--input PATH | -] [--iterations N] [--warmup N] [--json] [--wide]
+    aoc verify (--day N | --all) [--part 1|2] [--impl NAME | --alternatives]
+        [--wide]
+    aoc fetch (--day N | --all) [--year YEAR] [--base-url URL]
+    aoc examples --day N --html PATH
+    aoc generate --day N [--size N] [--seed N] [--output PATH]
+    aoc new --day N
+    aoc watch --day N [--input PATH]
+    aoc --help
+
+Missing inputs are downloaded using the session cookie in AOC_SESSION or
+~/.config/aoc/session, AOC_BASE_URL overrides the puzzle server.
+
+With --stream the input is solved while it is read, --all then only runs the
+days that support it. --trace shows the events emitted by the solvers on
+stderr. With --parallel the days are solved at the same time, by the parallel
+implementations where there are any. An answer that overflows 64 bits is an
+error, --wide solves in 128 bits where a day has wide implementations.
+
+generate writes a random input of about --size records, the same for the same
+--seed, to stdout or --output. new creates the crate of a day from a template
+and adds it to the workspace, the runner and the fuzz targets. watch runs the
+example tests of a day and solves its input each time a source, a fixture or
+the input is saved, comparing the answers to the previous ones and to the
+recorded ones.";
+
+/// Records in a generated input when no --size is given.
+const GENERATED_SIZE: usize = 1000;
+
+/// The input of one day, opened before solving so that only the solving
+/// happens in parallel.
+enum Source {
+    Text(String),
+    Reader(Box<dyn BufRead + Send>),
+}
+
+/// Timings of one phase of a day: parsing, or solving with one implementation.
+struct Measurement {
+    day: u8,
+    part: Option<Part>,
+    name: &'static str,
+    answer: Option<String>,
+    stats: Stats,
+}
+
+fn main() {
+    let result = Args::parse(env::args().skip(1)).and_then(|args| match args.command.as_deref() {
+        _ if args.flag("help") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some("run") => run(&args),
+        Some("bench") => bench(&args),
+        Some("verify") => verify(&args),
+        Some("fetch") => fetch(&args),
+        Some("examples") => extract_examples(&args),
+        Some("generate") => generate(&args),
+        Some("new") => new_day(&args),
+        Some("watch") => watch_day(&args),
+        Some(command) => Err(format!("unknown command `{}`\n{}", command, USAGE)),
+        None => Err(format!("expected a command\n{}", USAGE)),
+    });
+    if let Err(message) = result {
+        eprintln!("error: {}", message);
+        process::exit(2);
+    }
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    let streaming = args.flag("stream");
+    if streaming && (args.flag("impl") || args.flag("alternatives")) {
+        return Err("--stream cannot be combined with --impl or --alternatives".to_string());
+    }
+    if streaming && args.flag("wide") {
+        return Err("--stream always solves in the width of the day's answers".to_string());
+    }
+    let mut days = selected_days(args)?;
+    let missing = without_input(args, &mut days)?;
+    if streaming && args.flag("all") {
+        days.retain(Day::can_stream);
+    }
+    let parallel = args.flag("parallel");
+    if parallel && (args.flag("trace") || args.flag("trace-file")) {
+        return Err(
+            "--trace only sees the calling thread, it cannot be used with --parallel".into(),
+        );
+    }
+    let part = args.get::<Part>("part")?;
+    let tracer = Tracer::from_args(args)?;
+    let mut printer = Printer::new(args.get("format")?.unwrap_or(Format::Table));
+    let source = |day: &Day| {
+        if !streaming {
+            return read_input(args, day.day).map(Source::Text);
+        }
+        if !day.can_stream() {
+            return Err(format!("day {} cannot be streamed", day.day));
+        }
+        open_input(args, day.day).map(Source::Reader)
+    };
+    let solve_day = |day: &Day, source: Source| match source {
+        Source::Text(input) => solve(day, &input, part, args, &tracer),
+        Source::Reader(reader) => stream(day, part, reader, &tracer),
+    };
+    let mut report = |day: &Day, runs: Vec<Run>| {
+        if args.value("input").is_none() {
+            record_answers(day.day, &runs)?;
+        }
+        for run in runs.iter() {
+            printer.push(run);
+        }
+        Ok::<(), String>(())
+    };
+    if parallel {
+        let sources = days
+            .iter()
+            .map(source)
+            .collect::<Result<Vec<Source>, String>>()?;
+        let results = thread::scope(|scope| {
+            let solve_day = &solve_day;
+            days.iter()
+                .zip(sources)
+                .map(|(day, source)| scope.spawn(move || solve_day(day, source)))
+                .collect::<Vec<_>>()
+                .into_iter()
+                .map(|handle| handle.join().expect("solver panicked"))
+                .collect::<Vec<_>>()
+        });
+        for (day, runs) in days.iter().zip(results) {
+            report(day, runs?)?;
+        }
+    } else {
+        for day in days.iter() {
+            report(day, solve_day(day, source(day)?)?)?;
+        }
+    }
+    for &day in missing.iter() {
+        printer.missing(day);
+    }
+    printer.finish();
+    Ok(())
+}
+
+/// Locks in the first answer of each part solved from the real input.
+fn record_answers(day: u8, runs: &[Run]) -> Result<(), String> {
+    let path = answers_path(day);
+    let mut answers = Answers::load(&path)?;
+    for run in runs.iter().filter(|run| !run.alternative) {
+        if answers.get(run.part).is_none() {
+            answers.record(run.part, &run.answer)?;
+            eprintln!(
+                "recorded day {} part {}: {} in {}",
+                day,
+                run.part,
+                run.answer,
+                path.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Solves the real inputs and checks them against the recorded answers.
+fn verify(args: &Args) -> Result<(), String> {
+    if args.value("input").is_some() {
+        return Err("verify always uses the recorded input.txt".to_string());
+    }
+    let mut days = selected_days(args)?;
+    let missing = without_input(args, &mut days)?;
+    let part = args.get::<Part>("part")?;
+    let mut table = Table::new(&[
+        "day",
+        "part",
+        "implementation",
+        "answer",
+        "expected",
+        "status",
+    ]);
+    let (mut checked, mut failed) = (0, 0);
+    for day in days.iter() {
+        let input = read_input(args, day.day)?;
+        let answers = Answers::load(&answers_path(day.day))?;
+        for run in solve(day, &input, part, args, &Tracer::Off)? {
+            let expected = answers.get(run.part);
+            let status = match expected {
+                Some(expected) if expected == run.answer => "pass",
+                Some(_) => "FAIL",
+                None => "missing",
+            };
+            checked += 1;
+            if status == "FAIL" {
+                failed += 1;
+            }
+            table.push(vec![
+                run.day.to_string(),
+                run.part.to_string(),
+                run.name.to_string(),
+                run.answer,
+                expected.unwrap_or("-").to_string(),
+                status.to_string(),
+            ]);
+        }
+    }
+    for day in missing {
+        table.push(vec![
+            day.to_string(),
+            "-".to_string(),
+            "-".to_string(),
+            "-".to_string(),
+            "-".to_string(),
+            "no input".to_string(),
+        ]);
+    }
+    print!("{}", table);
+    if failed > 0 {
+        return Err(format!("{} of {} answers do not match", failed, checked));
+    }
+    Ok(())
+}
+
+fn selected_days(args: &Args) -> Result<Vec<Day>, String> {
+    let days = registry::days();
+    if args.flag("all") {
+        if args.value("input").is_some() {
+            return Err("--input cannot be combined with --all".to_string());
+        }
+        return Ok(days);
+    }
+    let Some(number) = args.get::<u8>("day")? else {
+        return Err(format!("expected --day N or --all\n{}", USAGE));
+    };
+    let day = days
+        .into_iter()
+        .find(|day| day.day == number)
+        .ok_or_else(|| format!("day {} is not solved", number))?;
+    Ok(vec![day])
+}
+
+fn root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("..")
+}
+
+fn day_dir(day: u8) -> PathBuf {
+    root().join(format!("day{}", day))
+}
+
+/// Where the puzzle inputs come from when `--input` is not given.
+/// With --all, takes the days whose input is neither cached nor downloadable
+/// out of `days`, so that the others still run, and returns them.
+fn without_input(args: &Args, days: &mut Vec<Day>) -> Result<Vec<u8>, String> {
+    if !args.flag("all") {
+        return Ok(vec![]);
+    }
+    let provider = provider(args)?;
+    let (available, missing) = days
+        .drain(..)
+        .partition::<Vec<Day>, _>(|day| provider.available(day.day));
+    *days = available;
+    let missing = missing.iter().map(|day| day.day).collect::<Vec<u8>>();
+    for day in missing.iter() {
+        eprintln!(
+            "skipping day {}: {} is missing and no session cookie is set",
+            day,
+            provider.input_path(*day).display()
+        );
+    }
+    Ok(missing)
+}
+
+fn provider(args: &Args) -> Result<Provider, String> {
+    let base_url = match args.value("base-url") {
+        Some(url) => url.to_string(),
+        None => env::var("AOC_BASE_URL").unwrap_or_else(|_| fetch::BASE_URL.to_string()),
+    };
+    Ok(Provider {
+        root: root(),
+        base_url: base_url.trim_end_matches('/').to_string(),
+        year: args.get("year")?.unwrap_or(fetch::YEAR),
+        session: fetch::session(),
+        interval: fetch::INTERVAL,
+    })
+}
+
+/// Downloads the inputs that are not cached yet.
+fn fetch(args: &Args) -> Result<(), String> {
+    let days = if args.flag("all") {
+        registry::days().iter().map(|day| day.day).collect()
+    } else {
+        match args.get::<u8>("day")? {
+            Some(day @ 1..=25) => vec![day],
+            Some(day) => return Err(format!("there is no day {}", day)),
+            None => return Err(format!("expected --day N or --all\n{}", USAGE)),
+        }
+    };
+    let provider = provider(args)?;
+    for day in days {
+        let path = provider.input_path(day);
+        if provider.fetch(day)? {
+            println!("day {}: fetched {}", day, path.display());
+        } else {
+            println!("day {}: cached {}", day, path.display());
+        }
+    }
+    Ok(())
+}
+
+/// Turns the examples of a saved puzzle page into fixtures and a test table
+/// in the day's crate.
+fn extract_examples(args: &Args) -> Result<(), String> {
+    let Some(number) = args.get::<u8>("day")? else {
+        return Err(format!("expected --day N\n{}", USAGE));
+    };
+    let Some(path) = args.value("html") else {
+        return Err(format!("expected --html PATH\n{}", USAGE));
+    };
+    let dir = day_dir(number);
+    if !dir.is_dir() {
+        return Err(format!("{} does not exist", dir.display()));
+    }
+    let html = fs::read_to_string(path).map_err(|err| format!("cannot read {}: {}", path, err))?;
+    // Only blocks the day's parser accepts are inputs, once there is a parser.
+    let day = registry::days().into_iter().find(|day| day.day == number);
+    let examples = examples::extract(&html, |text| {
+        day.as_ref().is_none_or(|day| day.parse(text).is_ok())
+    });
+    if examples.is_empty() {
+        return Err(format!("no examples with answers in {}", path));
+    }
+    for file in examples::write(&dir, number, &examples)? {
+        println!("wrote {}", dir.join(file).display());
+    }
+    Ok(())
+}
+
+/// Writes a random input for a day, to benchmark on inputs of any size.
+fn generate(args: &Args) -> Result<(), String> {
+    if args.flag("all") {
+        return Err(format!("expected --day N\n{}", USAGE));
+    }
+    let day = selected_days(args)?.remove(0);
+    let size = args.get("size")?.unwrap_or(GENERATED_SIZE);
+    let input = day
+        .generate(args.get("seed")?.unwrap_or(0), size)
+        .ok_or_else(|| format!("day {} has no generator", day.day))?;
+    match args.value("output") {
+        Some(path) => {
+            fs::write(path, input).map_err(|err| format!("cannot write {}: {}", path, err))
+        }
+        None => io::stdout()
+            .write_all(input.as_bytes())
+            .map_err(|err| format!("cannot write stdout: {}", err)),
+    }
+}
+
+/// Starts a day: its crate, fixtures and registrations.
+fn new_day(args: &Args) -> Result<(), String> {
+    let day = match args.get::<u8>("day")? {
+        Some(day @ 1..=25) => day,
+        Some(day) => return Err(format!("there is no day {}", day)),
+        None => return Err(format!("expected --day N\n{}", USAGE)),
+    };
+    for path in scaffold::create(&root(), day)? {
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+/// Tests and solves a day on every save, until interrupted.
+fn watch_day(args: &Args) -> Result<(), String> {
+    let Some(day) = args.get::<u8>("day")? else {
+        return Err(format!("expected --day N\n{}", USAGE));
+    };
+    let dir = day_dir(day);
+    if !dir.is_dir() {
+        return Err(format!("{} does not exist", dir.display()));
+    }
+    let input = match args.value("input") {
+        Some("-") => return Err("watch needs an input file to solve again".to_string()),
+        Some(path) => PathBuf::from(path),
+        None => {
+            let provider = provider(args)?;
+            provider.fetch(day)?;
+            provider.input_path(day)
+        }
+    };
+    watch::watch(&root(), &dir, day, &input)
+}
+
+/// The accepted answers for the input cached by [`provider`].
+fn answers_path(day: u8) -> PathBuf {
+    day_dir(day).join("answers.toml")
+}
+
+fn read_input(args: &Args, day: u8) -> Result<String, String> {
+    match args.value("input") {
+        Some("-") => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|err| format!("cannot read stdin: {}", err))?;
+            Ok(input)
+        }
+        Some(path) => {
+            fs::read_to_string(path).map_err(|err| format!("cannot read {}: {}", path, err))
+        }
+        None => provider(args)?.input(day),
+    }
+}
+
+/// The input as a reader, so that it never has to be held in memory.
+fn open_input(args: &Args, day: u8) -> Result<Box<dyn BufRead + Send>, String> {
+    let path = match args.value("input") {
+        Some("-") => return Ok(Box::new(BufReader::new(io::stdin()))),
+        Some(path) => PathBuf::from(path),
+        None => {
+            let provider = provider(args)?;
+            provider.fetch(day)?;
+            provider.input_path(day)
+        }
+    };
+    let file =
+        File::open(&path).map_err(|err| format!("cannot read {}: {}", path.display(), err))?;
+    Ok(Box::new(BufReader::new(file)))
+}
+
+/// Solves both parts while reading the input, parse time is part of the
+/// solve time.
+fn stream(
+    day: &Day,
+    part: Option<Part>,
+    mut reader: Box<dyn BufRead + Send>,
+    tracer: &Tracer,
+) -> Result<Vec<Run>, String> {
+    let start = Instant::now();
+    let label = format!("day {} stream", day.day);
+    let (part1, part2) = tracer
+        .run(&label, || day.stream(&mut reader).unwrap())?
+        .map_err(|err| match err {
+            StreamError::Solve(err) => format!("day {} stream: {}", day.day, err),
+            err => err.to_string(),
+        })?;
+    let solve = start.elapsed();
+    let runs = [(Part::One, part1), (Part::Two, part2)]
+        .into_iter()
+        .filter(|(answer_part, _)| part.is_none_or(|part| *answer_part == part))
+        .map(|(part, answer)| Run {
+            day: day.day,
+            part,
+            name: "stream",
+            alternative: false,
+            answer,
+            parse: None,
+            solve,
+        })
+        .collect();
+    Ok(runs)
+}
+
+/// The implementations picked by `--part`, `--impl`, `--alternatives`,
+/// `--wide` and `--parallel`. A wide implementation is preferred over a
+/// parallel one.
+fn implementations<'a>(
+    day: &'a Day,
+    part: Option<Part>,
+    args: &'a Args,
+) -> impl Iterator<Item = &'a Implementation> {
+    let kinds = [
+        ("wide", args.flag("wide")),
+        ("parallel", args.flag("parallel")),
+    ];
+    day.implementations
+        .iter()
+        .filter(move |imp| part.is_none_or(|part| imp.part == part))
+        .filter(move |imp| match args.value("impl") {
+            Some(name) => imp.name == name,
+            None if args.flag("alternatives") => true,
+            None => {
+                let preferred = kinds
+                    .iter()
+                    .filter(|(_, on)| *on)
+                    .map(|(kind, _)| format!("part{}_{}", imp.part, kind))
+                    .find(|name| day.implementation(name).is_some());
+                match preferred {
+                    Some(name) => imp.name == name,
+                    None => !imp.alternative,
+                }
+            }
+        })
+}
+
+/// The error of an answer that did not fit, pointing at `--wide` when the day
+/// can solve the part in 128 bits.
+fn unsolved(day: &Day, imp: &Implementation, err: SolveError) -> String {
+    let wide = format!("part{}_wide", imp.part);
+    let hint =
+        if err == SolveError::Overflow && imp.name != wide && day.implementation(&wide).is_some() {
+            ", try --wide"
+        } else {
+            ""
+        };
+    format!(
+        "day {} part {} {}: {}{}",
+        day.day, imp.part, imp.name, err, hint
+    )
+}
+
+fn solve(
+    day: &Day,
+    input: &str,
+    part: Option<Part>,
+    args: &Args,
+    tracer: &Tracer,
+) -> Result<Vec<Run>, String> {
+    let start = Instant::now();
+    let parsed = day.parse(input).map_err(|err| err.to_string())?;
+    let parse = start.elapsed();
+    implementations(day, part, args)
+        .map(|imp| {
+            let label = format!("day {} part {} {}", day.day, imp.part, imp.name);
+            let start = Instant::now();
+            let answer = tracer
+                .run(&label, || imp.solve(&parsed))?
+                .map_err(|err| unsolved(day, imp, err))?;
+            Ok(Run {
+                day: day.day,
+                part: imp.part,
+                name: imp.name,
+                alternative: imp.alternative,
+                answer,
+                parse: Some(parse),
+                solve: start.elapsed(),
+            })
+        })
+        .collect()
+}
+
+fn bench(args: &Args) -> Result<(), String> {
+    let mut days = selected_days(args)?;
+    without_input(args, &mut days)?;
+    let part = args.get::<Part>("part")?;
+    let iterations = args.get::<usize>("iterations")?.unwrap_or(10);
+    let warmup = args.get::<usize>("warmup")?.unwrap_or(3);
+    if iterations == 0 {
+        return Err("--iterations must be at least 1".to_string());
+    }
+    let mut measurements = vec![];
+    for day in days.iter() {
+        let input = read_input(args, day.day)?;
+        let (parsed, stats) = measure(warmup, iterations, || day.parse(&input));
+        let parsed = parsed.map_err(|err| err.to_string())?;
+        measurements.push(Measurement {
+            day: day.day,
+            part: None,
+            name: "parse",
+            answer: None,
+            stats,
+        });
+        for imp in implementations(day, part, args) {
+            let (answer, stats) = measure(warmup, iterations, || imp.solve(&parsed));
+            let answer = answer.map_err(|err| unsolved(day, imp, err))?;
+            measurements.push(Measurement {
+                day: day.day,
+                part: Some(imp.part),
+                name: imp.name,
+                answer: Some(answer),
+                stats,
+            });
+        }
+    }
+    if args.flag("json") {
+        print!("{}", to_json(&measurements));
+        return Ok(());
+    }
+    let mut table = Table::new(&[
+        "day",
+        "part",
+        "implementation",
+        "answer",
+        "iterations",
+        "min",
+        "median",
+        "p95",
+        "mean",
+    ]);
+    for measurement in measurements {
+        let stats = measurement.stats;
+        table.push(vec![
+            measurement.day.to_string(),
+            measurement
+                .part
+                .map_or("-".to_string(), |part| part.to_string()),
+            measurement.name.to_string(),
+            measurement.answer.unwrap_or_default(),
+            stats.iterations.to_string(),
+            format!("{:.2?}", stats.min),
+            format!("{:.2?}", stats.median),
+            format!("{:.2?}", stats.p95),
+            format!("{:.2?}", stats.mean),
+        ]);
+    }
+    print!("{}", table);
+    Ok(())
+}
+
+/// A JSON array with one object per measurement, durations in nanoseconds.
+fn to_json(measurements: &[Measurement]) -> String {
+    let objects = measurements
+        .iter()
+        .map(|measurement| {
+            let stats = measurement.stats;
+            let mut fields = vec![
+                format!("\"day\": {}", measurement.day),
+                format!(
+                    "\"phase\": \"{}\"",
+                    if measurement.part.is_some() {
+                        "solve"
+                    } else {
+                        "parse"
+                    }
+                ),
+            ];
+            if let Some(part) = measurement.part {
+                fields.push(format!("\"part\": {}", part));
+                fields.push(format!("\"impl\": {}", json_string(measurement.name)));
+            }
+            if let Some(answer) = &measurement.answer {
+                fields.push(format!("\"answer\": {}", json_string(answer)));
+            }
+            fields.push(format!("\"iterations\": {}", stats.iterations));
+            for (name, duration) in [
+                ("min", stats.min),
+                ("median", stats.median),
+                ("p95", stats.p95),
+                ("mean", stats.mean),
+            ] {
+                fields.push(format!("\"{}_ns\": {}", name, duration.as_nanos()));
+            }
+            format!("  {{{}}}", fields.join(", "))
+        })
+        .collect::<Vec<String>>();
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
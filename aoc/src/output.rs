@@ -0,0 +1,227 @@
+use std::{str::FromStr, time::Duration};
+
+use aoc_core::Part;
+
+use crate::table::Table;
+
+/// One answer produced by one implementation.
+pub struct Run {
+    pub day: u8,
+    pub part: Part,
+    pub name: &'static str,
+    pub alternative: bool,
+    pub answer: String,
+    /// `None` when the input was parsed while solving it.
+    pub parse: Option<Duration>,
+    pub solve: Duration,
+}
+
+/// How `aoc run` prints its runs. All but the table are printed as soon as a
+/// day is solved, with durations in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Table,
+    /// JSON lines, one object per run.
+    Json,
+    Csv,
+    Tsv,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(format!("no format {}", s)),
+        }
+    }
+}
+
+const COLUMNS: [&str; 6] = ["day", "part", "impl", "answer", "parse_ns", "solve_ns"];
+
+pub struct Printer {
+    format: Format,
+    table: Table,
+}
+
+impl Printer {
+    pub fn new(format: Format) -> Self {
+        if let Some(header) = header(format) {
+            println!("{}", header);
+        }
+        Printer {
+            format,
+            table: Table::new(&["day", "part", "implementation", "answer", "parse", "solve"]),
+        }
+    }
+
+    pub fn push(&mut self, run: &Run) {
+        if self.format != Format::Table {
+            println!("{}", line(self.format, run));
+            return;
+        }
+        self.table.push(vec![
+            run.day.to_string(),
+            run.part.to_string(),
+            run.name.to_string(),
+            run.answer.clone(),
+            run.parse
+                .map_or("-".to_string(), |parse| format!("{:.2?}", parse)),
+            format!("{:.2?}", run.solve),
+        ]);
+    }
+
+    /// A day that was not solved for lack of an input, only listed in the
+    /// table.
+    pub fn missing(&mut self, day: u8) {
+        if self.format == Format::Table {
+            self.table.push(vec![
+                day.to_string(),
+                "-".to_string(),
+                "-".to_string(),
+                "no input".to_string(),
+                "-".to_string(),
+                "-".to_string(),
+            ]);
+        }
+    }
+
+    pub fn finish(self) {
+        if self.format == Format::Table {
+            print!("{}", self.table);
+        }
+    }
+}
+
+fn header(format: Format) -> Option<String> {
+    match format {
+        Format::Csv => Some(COLUMNS.join(",")),
+        Format::Tsv => Some(COLUMNS.join("\t")),
+        Format::Table | Format::Json => None,
+    }
+}
+
+fn line(format: Format, run: &Run) -> String {
+    let parse = run.parse.map(|parse| parse.as_nanos().to_string());
+    match format {
+        Format::Json => format!(
+            "{{\"day\": {}, \"part\": {}, \"impl\": {}, \"answer\": {}, \"parse_ns\": {}, \"solve_ns\": {}}}",
+            run.day,
+            run.part,
+            json_string(run.name),
+            json_string(&run.answer),
+            parse.as_deref().unwrap_or("null"),
+            run.solve.as_nanos()
+        ),
+        Format::Csv | Format::Tsv => {
+            let fields = [
+                run.day.to_string(),
+                run.part.to_string(),
+                run.name.to_string(),
+                run.answer.clone(),
+                parse.unwrap_or_default(),
+                run.solve.as_nanos().to_string(),
+            ];
+            if format == Format::Csv {
+                fields.map(|field| csv_field(&field)).join(",")
+            } else {
+                fields.map(|field| tsv_field(&field)).join("\t")
+            }
+        }
+        Format::Table => unreachable!("tables are printed as a whole"),
+    }
+}
+
+pub fn json_string(value: &str) -> String {
+    let mut json = String::from('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Quoted when it holds a comma, a quote or a line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Tabs, line breaks and backslashes escaped with a backslash.
+fn tsv_field(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(answer: &str, parse: Option<Duration>) -> Run {
+        Run {
+            day: 8,
+            part: Part::Two,
+            name: "part2",
+            alternative: false,
+            answer: answer.to_string(),
+            parse,
+            solve: Duration::from_micros(12),
+        }
+    }
+
+    #[test]
+    fn test_lines() {
+        let parsed = run("7309459565207", Some(Duration::from_nanos(300)));
+        assert_eq!(
+            line(Format::Json, &parsed),
+            "{\"day\": 8, \"part\": 2, \"impl\": \"part2\", \"answer\": \"7309459565207\", \
+             \"parse_ns\": 300, \"solve_ns\": 12000}"
+        );
+        assert_eq!(
+            line(Format::Csv, &parsed),
+            "8,2,part2,7309459565207,300,12000"
+        );
+        assert_eq!(
+            line(Format::Tsv, &parsed),
+            "8\t2\tpart2\t7309459565207\t300\t12000"
+        );
+        let streamed = run("a,\"b\"\tc", None);
+        assert!(
+            line(Format::Json, &streamed).contains("\"a,\\\"b\\\"\\u0009c\", \"parse_ns\": null")
+        );
+        assert_eq!(
+            line(Format::Csv, &streamed),
+            "8,2,part2,\"a,\"\"b\"\"\tc\",,12000"
+        );
+        assert_eq!(
+            line(Format::Tsv, &streamed),
+            "8\t2\tpart2\ta,\"b\"\\tc\t\t12000"
+        );
+    }
+
+    #[test]
+    fn test_header() {
+        assert_eq!(
+            header(Format::Csv).as_deref(),
+            Some("day,part,impl,answer,parse_ns,solve_ns")
+        );
+        assert_eq!(header(Format::Json), None);
+        assert_eq!("tsv".parse(), Ok(Format::Tsv));
+        assert!("xml".parse::<Format>().is_err());
+    }
+}
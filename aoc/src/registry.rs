@@ -0,0 +1,43 @@
+use aoc_core::Day;
+
+/// Every solved day, in order.
+pub fn days() -> Vec<Day> {
+    vec![
+        Day::streaming::<day1::Day1>(),
+        Day::streaming::<day2::Day2>(),
+        Day::of::<day3::Day3>(),
+        Day::streaming::<day4::Day4>(),
+        Day::streaming::<day5::Day5>(),
+        Day::of::<day6::Day6>(),
+        Day::streaming::<day7::Day7>(),
+        Day::of::<day8::Day8>(),
+        Day::streaming::<day9::Day9>(),
+        Day::of::<day10::Day10>(),
+        Day::of::<day11::Day11>(),
+        Day::streaming::<day12::Day12>(),
+        Day::streaming::<day13::Day13>(),
+        Day::of::<day14::Day14>(),
+        Day::streaming::<day15::Day15>(),
+        Day::of::<day16::Day16>(),
+        Day::of::<day17::Day17>(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generators() {
+        for day in days() {
+            // Days only just scaffolded by `aoc new` have none yet.
+            let Some(input) = day.generate(7, 5) else {
+                continue;
+            };
+            assert_eq!(input, day.generate(7, 5).unwrap());
+            if let Err(err) = day.parse(&input) {
+                panic!("generated input of day {} does not parse: {}", day.day, err);
+            }
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::examples;
+
+/// Creates the crate of a day that is not solved yet under `root`, with an
+/// empty example fixture and test table, and adds it wherever the days are
+/// listed: the workspace, the runner and the fuzz targets. An existing day
+/// is left alone. Returns the written paths.
+pub fn create(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    let dir = root.join(format!("day{}", day));
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+    // Every list is edited in memory first, so that nothing is written when
+    // one of them cannot be.
+    let dependency = format!("day{0} = {{ path = \"../day{0}\" }}", day);
+    let bin = format!(
+        "[[bin]]\nname = \"day{0}\"\npath = \"fuzz_targets/day{0}.rs\"\ntest = false\ndoc = false\nbench = false",
+        day
+    );
+    let fuzz = root.join("fuzz/Cargo.toml");
+    let mut edits = vec![
+        edit(&root.join("Cargo.toml"), |text| {
+            let member = format!("    \"day{}\",", day);
+            insert(text, "\n", &member, day, |day| format!("\"day{}\"", day))
+        })?,
+        edit(&root.join("aoc/Cargo.toml"), |text| {
+            insert(text, "\n", &dependency, day, |day| format!("day{} = ", day))
+        })?,
+        edit(&root.join("aoc/src/registry.rs"), |text| {
+            let entry = format!("        Day::of::<day{0}::Day{0}>(),", day);
+            insert(text, "\n", &entry, day, |day| format!("<day{}::", day))
+        })?,
+    ];
+    edits.push(edit(&fuzz, |text| {
+        let text = insert(text, "\n", &dependency, day, |day| format!("day{} = ", day))?;
+        insert(&text, "\n\n", &bin, day, |day| {
+            format!("name = \"day{}\"", day)
+        })
+    })?);
+
+    let files = [
+        (dir.join("Cargo.toml"), manifest(day)),
+        (dir.join("src/lib.rs"), library(day)),
+        (
+            dir.join("src/main.rs"),
+            format!(
+                "fn main() {{\n    aoc_core::run::<day{0}::Day{0}>(\"./input.txt\");\n}}\n",
+                day
+            ),
+        ),
+        (dir.join("input.dev.txt"), String::new()),
+        (
+            root.join(format!("fuzz/fuzz_targets/day{}.rs", day)),
+            format!(
+                "#![no_main]\n\nuse libfuzzer_sys::fuzz_target;\n\nfuzz_target!(|data: &[u8]| {{\n    \
+                 aoc_fuzz::solve::<day{0}::Day{0}>(data, &[]);\n}});\n",
+                day
+            ),
+        ),
+    ];
+    let mut written = vec![];
+    for (path, contents) in files {
+        write(&path, &contents)?;
+        written.push(path);
+    }
+    for file in examples::write(&dir, day, &[])? {
+        written.push(dir.join(file));
+    }
+    for (path, text) in edits {
+        write(&path, &text)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|err| format!("cannot create {}: {}", parent.display(), err))?;
+    }
+    fs::write(path, contents).map_err(|err| format!("cannot write {}: {}", path.display(), err))
+}
+
+/// `path` and its text after `change`.
+fn edit(
+    path: &Path,
+    change: impl FnOnce(&str) -> Result<String, String>,
+) -> Result<(PathBuf, String), String> {
+    let text = fs::read_to_string(path)
+        .map_err(|err| format!("cannot read {}: {}", path.display(), err))?;
+    let text = change(&text).map_err(|err| format!("{}: {}", path.display(), err))?;
+    Ok((path.to_path_buf(), text))
+}
+
+/// Inserts `entry` among the entries of `text`, lines or blocks split by
+/// `separator`, that name a day, keeping them in the order of the days. The
+/// entry of a day is the one containing `marker(day)`.
+fn insert(
+    text: &str,
+    separator: &str,
+    entry: &str,
+    day: u8,
+    marker: impl Fn(u8) -> String,
+) -> Result<String, String> {
+    let mut entries = text
+        .strip_suffix('\n')
+        .unwrap_or(text)
+        .split(separator)
+        .collect::<Vec<&str>>();
+    let day_of = |entry: &str| (1..=25).find(|&day| entry.contains(&marker(day)));
+    if entries.iter().any(|&entry| day_of(entry) == Some(day)) {
+        return Err(format!("day {} is already listed", day));
+    }
+    let indx = match entries
+        .iter()
+        .rposition(|&entry| day_of(entry).is_some_and(|other| other < day))
+    {
+        Some(indx) => indx + 1,
+        None => entries
+            .iter()
+            .position(|&entry| day_of(entry).is_some())
+            .ok_or("no days listed")?,
+    };
+    entries.insert(indx, entry);
+    Ok(entries.join(separator) + "\n")
+}
+
+fn manifest(day: u8) -> String {
+    format!(
+        "[package]\n\
+         name = \"day{day}\"\n\
+         version = \"0.1.0\"\n\
+         edition = \"2021\"\n\
+         \n\
+         # See more keys and their definitions at https://doc.rust-lang.org/cargo/reference/manifest.html\n\
+         \n\
+         [dependencies]\n\
+         aoc-core = {{ path = \"../aoc-core\" }}\n"
+    )
+}
+
+fn library(day: u8) -> String {
+    format!(
+        "use aoc_core::{{ParseError, Solution}};
+
+pub struct Day{day};
+
+impl Solution for Day{day} {{
+    const DAY: u8 = {day};
+    type Input = Vec<String>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {{
+        parse_input(input)
+    }}
+
+    fn part1(input: &Self::Input) -> u64 {{
+        part1(input)
+    }}
+
+    fn part2(input: &Self::Input) -> u64 {{
+        part2(input)
+    }}
+}}
+
+pub fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {{
+    Ok(input.lines().map(str::to_string).collect())
+}}
+
+pub fn part1(lines: &[String]) -> u64 {{
+    todo!(\"part 1 of {{}} lines\", lines.len())
+}}
+
+pub fn part2(lines: &[String]) -> u64 {{
+    todo!(\"part 2 of {{}} lines\", lines.len())
+}}
+"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert() {
+        let members = "members = [\n    \"aoc\",\n    \"day9\",\n    \"day12\",\n]\n";
+        let marker = |day| format!("\"day{}\"", day);
+        assert_eq!(
+            insert(members, "\n", "    \"day10\",", 10, marker).unwrap(),
+            "members = [\n    \"aoc\",\n    \"day9\",\n    \"day10\",\n    \"day12\",\n]\n"
+        );
+        assert_eq!(
+            insert(members, "\n", "    \"day1\",", 1, marker).unwrap(),
+            "members = [\n    \"aoc\",\n    \"day1\",\n    \"day9\",\n    \"day12\",\n]\n"
+        );
+        assert!(insert(members, "\n", "    \"day9\",", 9, marker).is_err());
+        let bins = "[[bin]]\nname = \"day9\"\n\n[[bin]]\nname = \"day12\"\n";
+        assert_eq!(
+            insert(bins, "\n\n", "[[bin]]\nname = \"day14\"", 14, |day| {
+                format!("name = \"day{}\"", day)
+            })
+            .unwrap(),
+            "[[bin]]\nname = \"day9\"\n\n[[bin]]\nname = \"day12\"\n\n[[bin]]\nname = \"day14\"\n"
+        );
+    }
+
+    #[test]
+    fn test_create() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let files = [
+            (
+                "Cargo.toml",
+                "[workspace]\nmembers = [\n    \"aoc\",\n    \"day9\",\n]\n",
+            ),
+            (
+                "aoc/Cargo.toml",
+                "[dependencies]\nday9 = { path = \"../day9\" }\n",
+            ),
+            (
+                "aoc/src/registry.rs",
+                "    vec![\n        Day::streaming::<day9::Day9>(),\n    ]\n",
+            ),
+            (
+                "fuzz/Cargo.toml",
+                "[dependencies]\nday9 = { path = \"../day9\" }\n\n[[bin]]\nname = \"day9\"\n",
+            ),
+        ];
+        for (file, text) in files {
+            fs::create_dir_all(root.join(file).parent().unwrap()).unwrap();
+            fs::write(root.join(file), text).unwrap();
+        }
+        let written = create(&root, 10).unwrap();
+        assert!(written.contains(&root.join("day10/src/lib.rs")));
+        assert!(written.contains(&root.join("day10/tests/examples.rs")));
+        assert_eq!(
+            fs::read_to_string(root.join("aoc/src/registry.rs")).unwrap(),
+            "    vec![\n        Day::streaming::<day9::Day9>(),\n        \
+             Day::of::<day10::Day10>(),\n    ]\n"
+        );
+        let fuzz = fs::read_to_string(root.join("fuzz/Cargo.toml")).unwrap();
+        assert!(fuzz.contains("day10 = { path = \"../day10\" }\n\n[[bin]]\nname = \"day9\""));
+        assert!(fuzz.ends_with(
+            "path = \"fuzz_targets/day10.rs\"\ntest = false\ndoc = false\nbench = false\n"
+        ));
+        let err = create(&root, 10).unwrap_err();
+        assert!(err.ends_with("day10 already exists"), "{}", err);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,99 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufWriter, Write},
+    path::PathBuf,
+};
+
+use aoc_core::trace::{self, Count, Print};
+
+use crate::args::Args;
+
+/// Where the solver events asked for with `--trace` or `--trace-file` go.
+pub enum Tracer {
+    Off,
+    /// Every event on stderr.
+    Print,
+    /// The number of events of each kind on stderr.
+    Count,
+    /// Every event appended to a file, which is emptied first.
+    File(PathBuf),
+}
+
+impl Tracer {
+    pub fn from_args(args: &Args) -> Result<Tracer, String> {
+        if args.flag("trace") && args.value("trace").is_none() {
+            return Err("--trace expects print|count".to_string());
+        }
+        if args.flag("trace-file") && args.value("trace-file").is_none() {
+            return Err("--trace-file expects a path".to_string());
+        }
+        match (args.value("trace"), args.value("trace-file")) {
+            (None, None) => Ok(Tracer::Off),
+            (Some("print"), None) => Ok(Tracer::Print),
+            (Some("count"), None) => Ok(Tracer::Count),
+            (None, Some(path)) => {
+                File::create(path).map_err(|err| format!("cannot write {}: {}", path, err))?;
+                Ok(Tracer::File(PathBuf::from(path)))
+            }
+            (Some(_), Some(_)) => Err("--trace cannot be combined with --trace-file".to_string()),
+            (Some(value), None) => Err(format!("invalid value `{}` for --trace", value)),
+        }
+    }
+
+    /// Runs `f`, labelling its events with `label`.
+    pub fn run<T>(&self, label: &str, f: impl FnOnce() -> T) -> Result<T, String> {
+        match self {
+            Tracer::Off => Ok(f()),
+            Tracer::Print => {
+                eprintln!("# {}", label);
+                Ok(trace::with(Print(io::stderr()), f).0)
+            }
+            Tracer::Count => {
+                let (output, count) = trace::with(Count::default(), f);
+                let counts = count
+                    .0
+                    .iter()
+                    .map(|(name, count)| format!("{} {}", count, name))
+                    .collect::<Vec<String>>();
+                eprintln!("# {}: {}", label, counts.join(", "));
+                Ok(output)
+            }
+            Tracer::File(path) => {
+                let err = |err: io::Error| format!("cannot write {}: {}", path.display(), err);
+                let mut file = OpenOptions::new().append(true).open(path).map_err(err)?;
+                writeln!(file, "# {}", label).map_err(err)?;
+                let (output, Print(mut file)) = trace::with(Print(BufWriter::new(file)), f);
+                file.flush().map_err(err)?;
+                Ok(output)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tracer(line: &str) -> Result<Tracer, String> {
+        Tracer::from_args(&Args::parse(line.split(' ').map(|arg| arg.to_string())).unwrap())
+    }
+
+    #[test]
+    fn test_from_args() {
+        assert!(matches!(tracer("run --day 7"), Ok(Tracer::Off)));
+        assert!(matches!(tracer("run --trace count"), Ok(Tracer::Count)));
+        assert_eq!(
+            tracer("run --trace --day 7").err().as_deref(),
+            Some("--trace expects print|count")
+        );
+        assert_eq!(
+            tracer("run --day 7 --trace").err().as_deref(),
+            Some("--trace expects print|count")
+        );
+        assert_eq!(
+            tracer("run --trace-file").err().as_deref(),
+            Some("--trace-file expects a path")
+        );
+        assert!(tracer("run --trace all").is_err());
+    }
+}
@@ -0,0 +1,247 @@
+use std::{
+    collections::HashMap,
+    env,
+    ffi::{OsStr, OsString},
+    fs, io,
+    path::{Path, PathBuf},
+    process::{Command, Output},
+    thread,
+    time::Duration,
+};
+
+use aoc_core::Part;
+use inotify::{Inotify, WatchDescriptor, WatchMask};
+
+use crate::answers::Answers;
+
+/// How long to wait for the rest of a save, editors often write a file in
+/// several steps.
+const SETTLE: Duration = Duration::from_millis(200);
+
+/// The answers of both parts printed by a day's binary.
+pub type Solved = [Option<String>; 2];
+
+/// Which changes in a watched directory matter.
+enum Filter {
+    /// Rust sources of the crate and its tests.
+    Sources,
+    /// The fixtures, the real input and the manifest of the day.
+    Inputs,
+    /// Only this file, an `--input` outside the day.
+    File(OsString),
+}
+
+impl Filter {
+    fn matches(&self, name: &OsStr) -> bool {
+        let text = name.to_string_lossy();
+        match self {
+            Filter::Sources => text.ends_with(".rs"),
+            Filter::Inputs => text.starts_with("input") || text == "Cargo.toml",
+            Filter::File(file) => name == file,
+        }
+    }
+}
+
+/// Runs the example tests of the day in `dir`, then solves `input`, and does
+/// it again whenever a source, a fixture or the input is saved. Answers are
+/// compared to those of the previous run and to the recorded ones. Only
+/// returns on errors.
+pub fn watch(root: &Path, dir: &Path, day: u8, input: &Path) -> Result<(), String> {
+    let err = |path: &Path, err: io::Error| format!("cannot watch {}: {}", path.display(), err);
+    let mut inotify = Inotify::init().map_err(|e| format!("cannot start inotify: {}", e))?;
+    let mask = WatchMask::CLOSE_WRITE | WatchMask::MOVED_TO | WatchMask::DELETE;
+    let mut filters: HashMap<WatchDescriptor, Filter> = HashMap::new();
+    for source in ["src", "tests"].map(|name| dir.join(name)) {
+        for path in directories(&source) {
+            let wd = inotify
+                .watches()
+                .add(&path, mask)
+                .map_err(|e| err(&path, e))?;
+            filters.insert(wd, Filter::Sources);
+        }
+    }
+    let wd = inotify.watches().add(dir, mask).map_err(|e| err(dir, e))?;
+    filters.insert(wd, Filter::Inputs);
+    if let (Some(parent), Some(file)) = (input.parent(), input.file_name()) {
+        if parent.canonicalize().ok() != dir.canonicalize().ok() {
+            let wd = inotify
+                .watches()
+                .add(parent, mask)
+                .map_err(|e| err(parent, e))?;
+            filters.insert(wd, Filter::File(file.to_os_string()));
+        }
+    }
+
+    let mut previous = None;
+    let mut buffer = [0; 4096];
+    loop {
+        println!("== day {}", day);
+        if let Some(solved) = run(root, dir, day, input)? {
+            let answers = Answers::load(&dir.join("answers.toml"))?;
+            for line in report(previous.as_ref(), &solved, &answers) {
+                println!("{}", line);
+            }
+            previous = Some(solved);
+        }
+        println!("waiting for changes");
+        loop {
+            let events = inotify
+                .read_events_blocking(&mut buffer)
+                .map_err(|e| format!("cannot read inotify events: {}", e))?;
+            let changed = events.into_iter().any(|event| {
+                let filter = filters.get(&event.wd);
+                event
+                    .name
+                    .is_some_and(|name| filter.is_some_and(|filter| filter.matches(name)))
+            });
+            if changed {
+                break;
+            }
+        }
+        thread::sleep(SETTLE);
+        // Drops what the rest of the save queued up.
+        while inotify
+            .read_events(&mut buffer)
+            .is_ok_and(|events| events.count() > 0)
+        {}
+    }
+}
+
+/// `dir` and the directories below it.
+fn directories(dir: &Path) -> Vec<PathBuf> {
+    if !dir.is_dir() {
+        return vec![];
+    }
+    let mut dirs = vec![dir.to_path_buf()];
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            dirs.extend(directories(&entry.path()));
+        }
+    }
+    dirs
+}
+
+/// Tests and solves the day with cargo. `None` when the tests fail or the
+/// input cannot be solved, cargo's output is shown then.
+fn run(root: &Path, dir: &Path, day: u8, input: &Path) -> Result<Option<Solved>, String> {
+    let package = format!("day{}", day);
+    let tests = cargo(root, &["test", "--quiet", "-p", &package])?;
+    if !tests.status.success() {
+        print!("{}", String::from_utf8_lossy(&tests.stdout));
+        eprint!("{}", String::from_utf8_lossy(&tests.stderr));
+        println!("example tests failed");
+        return Ok(None);
+    }
+    println!("example tests passed");
+    let input = input.to_string_lossy();
+    let solve = cargo(
+        root,
+        &["run", "--quiet", "--release", "-p", &package, "--", &input],
+    )?;
+    if !solve.status.success() {
+        eprint!("{}", String::from_utf8_lossy(&solve.stderr));
+        println!("cannot solve {} with {}", input, dir.display());
+        return Ok(None);
+    }
+    Ok(Some(parse_answers(&String::from_utf8_lossy(&solve.stdout))))
+}
+
+fn cargo(root: &Path, args: &[&str]) -> Result<Output, String> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    Command::new(cargo)
+        .args(args)
+        .current_dir(root)
+        .output()
+        .map_err(|err| format!("cannot run cargo {}: {}", args.join(" "), err))
+}
+
+/// The answers in the `part1: ...` and `part2: ...` lines of `aoc_core::run`.
+pub fn parse_answers(output: &str) -> Solved {
+    let mut solved: Solved = [None, None];
+    for line in output.lines() {
+        for (indx, prefix) in ["part1: ", "part2: "].iter().enumerate() {
+            if let Some(answer) = line.strip_prefix(prefix) {
+                solved[indx] = Some(answer.trim().to_string());
+            }
+        }
+    }
+    solved
+}
+
+/// One line per part: the answer, how it changed since `previous` and how it
+/// compares to the recorded one.
+pub fn report(previous: Option<&Solved>, solved: &Solved, recorded: &Answers) -> Vec<String> {
+    [Part::One, Part::Two]
+        .into_iter()
+        .zip(solved)
+        .enumerate()
+        .map(|(indx, (part, answer))| {
+            let mut notes = vec![];
+            if let Some(previous) = previous {
+                match (&previous[indx], answer) {
+                    (before, now) if before == now => notes.push("unchanged".to_string()),
+                    (Some(before), _) => notes.push(format!("was {}", before)),
+                    (None, _) => notes.push("new".to_string()),
+                }
+            }
+            notes.push(match (recorded.get(part), answer) {
+                (Some(expected), Some(answer)) if expected == answer => {
+                    "matches answers.toml".to_string()
+                }
+                (Some(expected), _) => format!("answers.toml has {}", expected),
+                (None, _) => "not recorded".to_string(),
+            });
+            format!(
+                "part {}: {} ({})",
+                part,
+                answer.as_deref().unwrap_or("-"),
+                notes.join(", ")
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() {
+        assert_eq!(
+            parse_answers("part1: 7032\npart2: 1493340882140\n"),
+            [Some("7032".to_string()), Some("1493340882140".to_string())]
+        );
+        assert_eq!(
+            parse_answers("warning\npart1: 1\n"),
+            [Some("1".to_string()), None]
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let path = env::temp_dir().join(format!("aoc-watch-{}.toml", std::process::id()));
+        fs::write(&path, "part1 = \"7032\"\n").unwrap();
+        let recorded = Answers::load(&path).unwrap();
+        let solved = [Some("7032".to_string()), Some("12".to_string())];
+        assert_eq!(
+            report(None, &solved, &recorded),
+            [
+                "part 1: 7032 (matches answers.toml)",
+                "part 2: 12 (not recorded)"
+            ]
+        );
+        let previous = [Some("7031".to_string()), Some("12".to_string())];
+        assert_eq!(
+            report(Some(&previous), &previous, &recorded),
+            [
+                "part 1: 7031 (unchanged, answers.toml has 7032)",
+                "part 2: 12 (unchanged, not recorded)"
+            ]
+        );
+        assert_eq!(
+            report(Some(&previous), &solved, &recorded)[0],
+            "part 1: 7032 (was 7031, matches answers.toml)"
+        );
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,285 @@
+use std::{error::Error, fmt};
+
+use aoc_core::{
+    generate::Rng,
+    num::{Answer, Int, Overflow, SolveError, Total},
+    Alternative, ParseError, Part, Solution, Streaming,
+};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    type Input = Vec<String>;
+    type Answer1 = Result<u64, CalibrationError>;
+    type Answer2 = Result<u64, CalibrationError>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64, CalibrationError> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64, CalibrationError> {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![
+            Alternative {
+                part: Part::One,
+                name: "part1_wide",
+                solve: |input| part1::<u128>(input).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_wide",
+                solve: |input| part2::<u128>(input).answer(),
+            },
+        ]
+    }
+}
+
+impl Streaming for Day1 {
+    type State = (Total<u64, CalibrationError>, Total<u64, CalibrationError>);
+
+    fn feed(state: &mut Self::State, record: &str) -> Result<(), ParseError> {
+        let parsed = parse_input(record)?;
+        state.0.add(part1(&parsed));
+        state.1.add(part2(&parsed));
+        Ok(())
+    }
+
+    fn finish(state: Self::State) -> Result<(Self::Answer1, Self::Answer2), ParseError> {
+        Ok((state.0.get(), state.1.get()))
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                return Err(ParseError::at(Day1::DAY, input, line, "a calibration line"));
+            }
+            match line.find(|c: char| !c.is_ascii_alphanumeric()) {
+                Some(indx) => Err(ParseError::at(
+                    Day1::DAY,
+                    input,
+                    &line[indx..],
+                    "a digit or a letter",
+                )),
+                None => Ok(line.to_string()),
+            }
+        })
+        .collect()
+}
+
+/// Why a part has no answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalibrationError {
+    Overflow,
+    /// A line without a digit, as the part reads digits, has no calibration
+    /// value. Part 2 lines may spell all their digits out, so the parser
+    /// lets such lines through.
+    NoDigit,
+}
+
+impl fmt::Display for CalibrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalibrationError::Overflow => fmt::Display::fmt(&Overflow, f),
+            CalibrationError::NoDigit => write!(f, "a line has no calibration digit"),
+        }
+    }
+}
+
+impl Error for CalibrationError {}
+
+impl From<Overflow> for CalibrationError {
+    fn from(_: Overflow) -> Self {
+        CalibrationError::Overflow
+    }
+}
+
+impl From<CalibrationError> for SolveError {
+    fn from(err: CalibrationError) -> Self {
+        match err {
+            CalibrationError::Overflow => SolveError::Overflow,
+            CalibrationError::NoDigit => SolveError::NoAnswer(err.to_string()),
+        }
+    }
+}
+
+pub fn part1<N: Int>(input: &[String]) -> Result<N, CalibrationError> {
+    N::try_sum(input.iter().map(|x| {
+        let first_num = x
+            .chars()
+            .find_map(|y| y.to_digit(10))
+            .ok_or(CalibrationError::NoDigit)?;
+        let last_num = x
+            .chars()
+            .rev()
+            .find_map(|y| y.to_digit(10))
+            .ok_or(CalibrationError::NoDigit)?;
+
+        Ok(N::of(u64::from(first_num * 10 + last_num))?)
+    }))
+}
+
+const NUMS: [&str; 9] = [
+    "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+pub fn part2<N: Int>(input: &[String]) -> Result<N, CalibrationError> {
+    let digits = input.iter().map(|line| {
+        let mut min_index_till_now = usize::MAX;
+        let mut min_num_till_now = 0_usize;
+        let mut min_index_till_now_rev = usize::MAX;
+        let mut min_num_till_now_rev = 0_usize;
+        let f_num_opt = line.chars().enumerate().find(|(_, y)| y.is_ascii_digit());
+        let l_num_opt = line
+            .chars()
+            .rev()
+            .enumerate()
+            .find(|(_, y)| y.is_ascii_digit());
+        NUMS.iter().enumerate().for_each(|(idx, &num)| {
+            if let Some((matched_index, _)) = line.match_indices(num).next() {
+                if matched_index < min_index_till_now {
+                    min_index_till_now = matched_index;
+                    min_num_till_now = idx + 1;
+                }
+            }
+            let reversed_line = line.chars().rev().collect::<String>();
+            let reversed_num = num.chars().rev().collect::<String>();
+            if let Some((matched_index, _)) =
+                reversed_line.match_indices(reversed_num.as_str()).next()
+            {
+                if matched_index < min_index_till_now_rev {
+                    min_index_till_now_rev = matched_index;
+                    min_num_till_now_rev = idx + 1;
+                }
+            }
+        });
+        if let Some((first_num_idx, first_num)) = f_num_opt {
+            if first_num_idx < min_index_till_now {
+                min_num_till_now = first_num.to_digit(10).unwrap() as usize;
+            }
+        }
+        if let Some((last_num_idx, last_num)) = l_num_opt {
+            if last_num_idx < min_index_till_now_rev {
+                min_num_till_now_rev = last_num.to_digit(10).unwrap() as usize;
+            }
+        }
+        if min_num_till_now == 0 {
+            return Err(CalibrationError::NoDigit);
+        }
+        Ok((min_num_till_now, min_num_till_now_rev))
+    });
+    N::try_sum(digits.map(|digits| {
+        let (first, last) = digits?;
+        Ok(N::of((first * 10 + last) as u64)?)
+    }))
+}
+
+/// `size` calibration lines mixing letters, digits and spelled out digits,
+/// which sometimes overlap as in `eightwo`. Every line has a digit.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let pieces = rng.range(2..=8);
+        let digit = rng.below(pieces);
+        for piece in 0..pieces {
+            if piece == digit || rng.one_in(4) {
+                input.push(char::from(b'0' + rng.range(1..=9) as u8));
+            } else if rng.one_in(3) {
+                let word = *rng.pick(&NUMS);
+                // Drop the first letter when it is the last one of the line.
+                let shared = input.ends_with(&word[..1]) && rng.one_in(2);
+                input.push_str(&word[usize::from(shared)..]);
+            } else {
+                for _ in 0..rng.range(1..=4) {
+                    input.push(char::from(b'a' + rng.below(26) as u8));
+                }
+            }
+        }
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use aoc_core::stream;
+
+    use super::*;
+
+    #[test]
+    fn test_dev_part1() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Cannot read file to string");
+        assert_eq!(part1::<u64>(&parse_input(&input).unwrap()), Ok(142));
+    }
+    #[test]
+    fn test_dev_part2() {
+        let input =
+            fs::read_to_string("./input_part2.dev.txt").expect("Cannot read file to string");
+        assert_eq!(part2::<u64>(&parse_input(&input).unwrap()), Ok(281));
+    }
+
+    #[test]
+    fn test_widths() {
+        let parsed = parse_input(&"9\n".repeat(1000)).unwrap();
+        assert_eq!(part1::<i32>(&parsed), Ok(99_000));
+        assert_eq!(part2::<u128>(&parsed), Ok(99_000));
+    }
+
+    #[test]
+    fn test_no_digit() {
+        let parsed = parse_input("eightwothree\n").unwrap();
+        assert_eq!(part1::<u64>(&parsed), Err(CalibrationError::NoDigit));
+        assert_eq!(part2::<u64>(&parsed), Ok(83));
+        let parsed = parse_input("1abc2\nabc\n").unwrap();
+        assert_eq!(part1::<u64>(&parsed), Err(CalibrationError::NoDigit));
+        assert_eq!(part2::<u64>(&parsed), Err(CalibrationError::NoDigit));
+    }
+
+    #[test]
+    fn test_stream() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Cannot read file to string");
+        let parsed = parse_input(&input).unwrap();
+        assert_eq!(
+            stream::solve::<Day1>(input.as_bytes()).unwrap(),
+            (part1(&parsed), part2(&parsed))
+        );
+    }
+
+    #[test]
+    fn test_stream_blank_line() {
+        let input = "1abc2\n\npqr3stu8vwx\n";
+        let err = parse_input(input).unwrap_err();
+        let Err(stream::StreamError::Parse(streamed)) = stream::solve::<Day1>(input.as_bytes())
+        else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((streamed.line, streamed.column), (err.line, err.column));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 100);
+        assert_eq!(input, generate(&mut Rng::new(1), 100));
+        let parsed = parse_input(&input).unwrap();
+        assert_eq!(parsed.len(), 100);
+        assert_eq!(
+            stream::solve::<Day1>(input.as_bytes()).unwrap(),
+            (part1(&parsed), part2(&parsed))
+        );
+    }
+}
@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+
+use aoc_core::{
+    generate::Rng,
+    grid::{Pos, DOWN, LEFT, ORTHOGONAL, RIGHT, UP},
+    num::Answer,
+    Alternative, Grid, ParseError, Part, Solution,
+};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = Maze;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> u64 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> u64 {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![Alternative {
+            part: Part::Two,
+            name: "part2_scanline",
+            solve: |input| part2_scanline(input).answer(),
+        }]
+    }
+}
+
+const PIPES: [char; 6] = ['|', '-', 'L', 'J', '7', 'F'];
+
+/// The tiles, with the pipe hidden under the start filled in.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Maze {
+    pub grid: Grid<char>,
+    pub start: Pos,
+}
+
+/// The directions `tile` connects to.
+fn connections(tile: char) -> &'static [(isize, isize)] {
+    match tile {
+        '|' => &[UP, DOWN],
+        '-' => &[LEFT, RIGHT],
+        'L' => &[UP, RIGHT],
+        'J' => &[UP, LEFT],
+        '7' => &[DOWN, LEFT],
+        'F' => &[DOWN, RIGHT],
+        _ => &[],
+    }
+}
+
+fn opposite(direction: (isize, isize)) -> (isize, isize) {
+    (-direction.0, -direction.1)
+}
+
+/// The pipe connecting exactly the directions `ends`.
+fn pipe(ends: &[(isize, isize)]) -> Option<char> {
+    PIPES
+        .into_iter()
+        .find(|&pipe| ends.len() == 2 && ends.iter().all(|end| connections(pipe).contains(end)))
+}
+
+pub fn parse_input(input: &str) -> Result<Maze, ParseError> {
+    let mut grid = Grid::parse(Day10::DAY, input, "a pipe, `.` or `S`", |c| {
+        (PIPES.contains(&c) || c == '.' || c == 'S').then_some(c)
+    })?;
+    let starts = input
+        .match_indices('S')
+        .map(|(indx, _)| indx)
+        .collect::<Vec<_>>();
+    let at_start = |expected: &str| ParseError::new(Day10::DAY, input, starts[0], expected);
+    match starts[..] {
+        [] => return Err(ParseError::new(Day10::DAY, input, 0, "a start `S`")),
+        [_] => {}
+        [_, second, ..] => return Err(ParseError::new(Day10::DAY, input, second, "one start")),
+    }
+    let start = grid.find_all(|&tile| tile == 'S').next().unwrap();
+    let joined = ORTHOGONAL
+        .into_iter()
+        .filter(|&direction| {
+            grid.offset(start, direction)
+                .is_some_and(|next| connections(grid[next]).contains(&opposite(direction)))
+        })
+        .collect::<Vec<(isize, isize)>>();
+    grid[start] = pipe(&joined).ok_or_else(|| at_start("a start joined by two pipes"))?;
+    let maze = Maze { grid, start };
+    if trace(&maze).is_none() {
+        return Err(at_start("a start on a loop"));
+    }
+    Ok(maze)
+}
+
+/// The tiles of the loop in order, from the start. `None` if the pipes from
+/// the start do not lead back to it.
+fn trace(maze: &Maze) -> Option<Vec<Pos>> {
+    let mut tiles = vec![maze.start];
+    let mut pos = maze.start;
+    let mut direction = connections(maze.grid[pos])[0];
+    loop {
+        pos = maze.grid.offset(pos, direction)?;
+        if pos == maze.start {
+            return Some(tiles);
+        }
+        let back = opposite(direction);
+        let exits = connections(maze.grid[pos]);
+        if !exits.contains(&back) {
+            return None;
+        }
+        direction = exits.iter().copied().find(|&exit| exit != back)?;
+        tiles.push(pos);
+    }
+}
+
+/// The loop of a parsed maze, which always closes.
+pub fn pipe_loop(maze: &Maze) -> Vec<Pos> {
+    trace(maze).expect("start not on a loop")
+}
+
+/// The farthest tile is halfway around the loop.
+pub fn part1(maze: &Maze) -> u64 {
+    pipe_loop(maze).len() as u64 / 2
+}
+
+/// Counts the enclosed tiles with Pick's theorem: the shoelace formula gives
+/// the area of the polygon through the centres of the loop's tiles, which are
+/// its boundary points.
+pub fn part2(maze: &Maze) -> u64 {
+    let tiles = pipe_loop(maze);
+    let twice_area = tiles
+        .iter()
+        .zip(tiles.iter().cycle().skip(1))
+        .map(|(&(row, col), &(next_row, next_col))| {
+            (row * next_col) as i64 - (next_row * col) as i64
+        })
+        .sum::<i64>()
+        .unsigned_abs();
+    (twice_area + 2 - tiles.len() as u64) / 2
+}
+
+/// Same as [`part2`], scanning each row: a tile off the loop is enclosed when
+/// the loop tiles left of it connect upwards an odd number of times.
+pub fn part2_scanline(maze: &Maze) -> u64 {
+    let grid = &maze.grid;
+    let mut on_loop = Grid::filled(grid.rows(), grid.cols(), false);
+    for tile in pipe_loop(maze) {
+        on_loop[tile] = true;
+    }
+    let mut enclosed = 0;
+    for row in 0..grid.rows() {
+        let mut inside = false;
+        for col in 0..grid.cols() {
+            if !on_loop[(row, col)] {
+                enclosed += u64::from(inside);
+            } else if connections(grid[(row, col)]).contains(&UP) {
+                inside = !inside;
+            }
+        }
+    }
+    enclosed
+}
+
+/// Whether adding `pos` to `blob` would make it touch another cell only at a
+/// corner, where its outline would cross itself.
+fn touches_corner(blob: &Grid<bool>, pos: Pos) -> bool {
+    [(-1, -1), (-1, 1), (1, 1), (1, -1)]
+        .into_iter()
+        .any(|(row, col)| {
+            blob.offset(pos, (row, col))
+                .is_some_and(|corner| blob[corner])
+                && !blob[blob.offset(pos, (row, 0)).unwrap()]
+                && !blob[blob.offset(pos, (0, col)).unwrap()]
+        })
+}
+
+/// A maze of about `size` rows and columns whose loop is the outline of a
+/// random blob of cells, drawn at twice the scale so that the loop never runs
+/// next to itself. The other tiles are random, except that none of them
+/// points at the start.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let cells = (size / 2).max(2);
+    let mut blob = Grid::filled(cells, cells, false);
+    let centre = (cells / 2, cells / 2);
+    blob[centre] = true;
+    let mut frontier = blob.neighbours4(centre).collect::<Vec<Pos>>();
+    for _ in 1..cells * cells / 2 {
+        let pos = loop {
+            if frontier.is_empty() {
+                break None;
+            }
+            let pos = frontier.swap_remove(rng.below(frontier.len() as u64) as usize);
+            if !blob[pos] && !touches_corner(&blob, pos) {
+                break Some(pos);
+            }
+        };
+        let Some(pos) = pos else {
+            break;
+        };
+        blob[pos] = true;
+        frontier.extend(blob.neighbours4(pos).filter(|&next| !blob[next]));
+    }
+
+    // The outline goes clockwise around the cells, between the corners of
+    // the grid. Holes in the blob have outlines of their own, which are left
+    // out by starting on the outside.
+    let mut outline: HashMap<Pos, Pos> = HashMap::new();
+    for ((row, col), _) in blob.iter().filter(|(_, &cell)| cell) {
+        let outside = |direction| {
+            blob.offset((row, col), direction)
+                .is_none_or(|next| !blob[next])
+        };
+        let corners = [
+            (row, col),
+            (row, col + 1),
+            (row + 1, col + 1),
+            (row + 1, col),
+        ];
+        for (side, direction) in [UP, RIGHT, DOWN, LEFT].into_iter().enumerate() {
+            if outside(direction) {
+                outline.insert(corners[side], corners[(side + 1) % 4]);
+            }
+        }
+    }
+    let first = blob.find_all(|&cell| cell).next().unwrap();
+    let mut tiles = vec![];
+    let mut corner = first;
+    loop {
+        let next = outline[&corner];
+        tiles.push((2 * corner.0, 2 * corner.1));
+        tiles.push((corner.0 + next.0, corner.1 + next.1));
+        corner = next;
+        if corner == first {
+            break;
+        }
+    }
+
+    let side = 2 * cells + 1;
+    let mut grid = Grid::filled(side, side, '.');
+    for tile in grid.find_all(|_| true).collect::<Vec<Pos>>() {
+        if !rng.one_in(3) {
+            grid[tile] = *rng.pick(&PIPES);
+        }
+    }
+    let direction = |from: Pos, to: Pos| {
+        (
+            to.0 as isize - from.0 as isize,
+            to.1 as isize - from.1 as isize,
+        )
+    };
+    for (indx, &tile) in tiles.iter().enumerate() {
+        let before = tiles[(indx + tiles.len() - 1) % tiles.len()];
+        let after = tiles[(indx + 1) % tiles.len()];
+        grid[tile] = pipe(&[direction(tile, before), direction(tile, after)]).unwrap();
+    }
+    let start = *rng.pick(&tiles);
+    for neighbour in grid.neighbours4(start).collect::<Vec<Pos>>() {
+        let towards = direction(neighbour, start);
+        if !tiles.contains(&neighbour) && connections(grid[neighbour]).contains(&towards) {
+            grid[neighbour] = '.';
+        }
+    }
+    grid[start] = 'S';
+    grid.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use aoc_core::{check, generate::Rng, Day};
+
+    use super::*;
+
+    #[test]
+    fn test_start_pipe() {
+        let input = fs::read_to_string("./input.dev.example2.txt").expect("Cannot read file");
+        let maze = parse_input(&input).unwrap();
+        assert_eq!((maze.start, maze.grid[maze.start]), ((1, 1), 'F'));
+        let input = fs::read_to_string("./input.dev.example8.txt").expect("Cannot read file");
+        let maze = parse_input(&input).unwrap();
+        assert_eq!((maze.start, maze.grid[maze.start]), ((0, 4), '7'));
+        assert_eq!(pipe_loop(&maze)[..3], [(0, 4), (1, 4), (2, 4)]);
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("S-7\n|.|\nL-.\n").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 1, "a start on a loop")
+        );
+        let err = parse_input("S-7\n|.X\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+        let err = parse_input(".S.\n...\n").unwrap_err();
+        assert_eq!(err.expected, "a start joined by two pipes");
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 60);
+        assert_eq!(input, generate(&mut Rng::new(1), 60));
+        let maze = parse_input(&input).unwrap();
+        assert_eq!(maze.grid.rows(), 61);
+        assert!(part1(&maze) > 60);
+        check::agree(&Day::of::<Day10>(), &input).unwrap();
+    }
+}
@@ -0,0 +1,3 @@
+fn main() {
+    aoc_core::run::<day10::Day10>("./input.txt");
+}
@@ -0,0 +1,61 @@
+//! The examples of the puzzle description, and a loop that encloses no tile.
+
+use aoc_core::{example::assert_examples, Day, Example, Part};
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: Part::One,
+        file: "input.dev.example1.txt",
+        answer: "4",
+    },
+    Example {
+        part: Part::One,
+        file: "input.dev.example2.txt",
+        answer: "4",
+    },
+    Example {
+        part: Part::One,
+        file: "input.dev.example3.txt",
+        answer: "8",
+    },
+    Example {
+        part: Part::One,
+        file: "input.dev.example4.txt",
+        answer: "8",
+    },
+    Example {
+        part: Part::Two,
+        file: "input.dev.example1.txt",
+        answer: "1",
+    },
+    Example {
+        part: Part::Two,
+        file: "input.dev.example5.txt",
+        answer: "4",
+    },
+    Example {
+        part: Part::Two,
+        file: "input.dev.example6.txt",
+        answer: "4",
+    },
+    Example {
+        part: Part::Two,
+        file: "input.dev.example7.txt",
+        answer: "8",
+    },
+    Example {
+        part: Part::Two,
+        file: "input.dev.example8.txt",
+        answer: "10",
+    },
+    Example {
+        part: Part::Two,
+        file: "input.dev.empty.txt",
+        answer: "0",
+    },
+];
+
+#[test]
+fn test_examples() {
+    assert_examples(&Day::of::<day10::Day10>(), EXAMPLES);
+}
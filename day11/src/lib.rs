@@ -0,0 +1,173 @@
+use std::{collections::HashSet, num::NonZeroU64};
+
+use aoc_core::{
+    generate::Rng,
+    grid::Pos,
+    num::{Answer, Int, Overflow},
+    Alternative, Grid, ParseError, Part, Solution,
+};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = Vec<Pos>;
+    type Answer1 = Result<u64, Overflow>;
+    type Answer2 = Result<u64, Overflow>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64, Overflow> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64, Overflow> {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![
+            Alternative {
+                part: Part::One,
+                name: "part1_pairs",
+                solve: |input| distances_pairs(input, YOUNG).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_pairs",
+                solve: |input| distances_pairs(input, OLD).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_wide",
+                solve: |input| part2::<u128>(input).answer(),
+            },
+        ]
+    }
+}
+
+/// The positions of the galaxies, row by row.
+pub fn parse_input(input: &str) -> Result<Vec<Pos>, ParseError> {
+    let image = Grid::parse(Day11::DAY, input, "`.` or `#`", |c| {
+        matches!(c, '.' | '#').then_some(c)
+    })?;
+    Ok(image.find_all(|&c| c == '#').collect())
+}
+
+/// How many times as wide empty rows and columns grow in each part.
+const YOUNG: NonZeroU64 = NonZeroU64::new(2).unwrap();
+const OLD: NonZeroU64 = NonZeroU64::new(1_000_000).unwrap();
+
+/// The sum of the distances along one axis between all pairs of `coords`,
+/// each coordinate without a galaxy counting `factor` times. Once sorted, the
+/// distances of a coordinate to those before it add up from a prefix sum.
+fn axis<N: Int>(mut coords: Vec<usize>, factor: NonZeroU64) -> Result<N, Overflow> {
+    coords.sort_unstable();
+    let growth = N::of(factor.get() - 1)?;
+    let mut total = N::ZERO;
+    let mut prefix = N::ZERO;
+    let mut occupied = 0;
+    for (indx, &coord) in coords.iter().enumerate() {
+        if indx == 0 || coords[indx - 1] != coord {
+            occupied += 1;
+        }
+        let empty = N::of((coord + 1 - occupied) as u64)?;
+        let expanded = N::of(coord as u64)?.try_add(empty.try_mul(growth)?)?;
+        let before = expanded.try_mul(N::of(indx as u64)?)?.try_sub(prefix)?;
+        total = total.try_add(before)?;
+        prefix = prefix.try_add(expanded)?;
+    }
+    Ok(total)
+}
+
+/// The sum of the shortest paths between all pairs of galaxies, once every
+/// empty row and column is `factor` times as wide.
+pub fn distances<N: Int>(galaxies: &[Pos], factor: NonZeroU64) -> Result<N, Overflow> {
+    let rows = axis::<N>(galaxies.iter().map(|&(row, _)| row).collect(), factor)?;
+    let cols = axis::<N>(galaxies.iter().map(|&(_, col)| col).collect(), factor)?;
+    rows.try_add(cols)
+}
+
+pub fn part1<N: Int>(galaxies: &[Pos]) -> Result<N, Overflow> {
+    distances(galaxies, YOUNG)
+}
+
+pub fn part2<N: Int>(galaxies: &[Pos]) -> Result<N, Overflow> {
+    distances(galaxies, OLD)
+}
+
+/// Reference for [`distances`]: moves every galaxy to where the expansion
+/// puts it, then measures each pair.
+pub fn distances_pairs(galaxies: &[Pos], factor: NonZeroU64) -> Result<u64, Overflow> {
+    let growth = factor.get() - 1;
+    let expand = |coords: Vec<usize>| {
+        let occupied = coords.iter().copied().collect::<HashSet<usize>>();
+        coords
+            .iter()
+            .map(|&coord| {
+                let empty = (0..coord).filter(|other| !occupied.contains(other)).count();
+                (coord as u64).try_add((empty as u64).try_mul(growth)?)
+            })
+            .collect::<Result<Vec<u64>, Overflow>>()
+    };
+    let rows = expand(galaxies.iter().map(|&(row, _)| row).collect())?;
+    let cols = expand(galaxies.iter().map(|&(_, col)| col).collect())?;
+    u64::try_sum((0..galaxies.len()).flat_map(|first| {
+        let (rows, cols) = (&rows, &cols);
+        (first + 1..galaxies.len()).map(move |second| {
+            rows[first]
+                .abs_diff(rows[second])
+                .try_add(cols[first].abs_diff(cols[second]))
+        })
+    }))
+}
+
+/// An image of `size` rows and columns, about one in ten of them empty and
+/// one in twenty of the other cells a galaxy.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let empty_rows = (0..size).map(|_| rng.one_in(10)).collect::<Vec<bool>>();
+    let empty_cols = (0..size).map(|_| rng.one_in(10)).collect::<Vec<bool>>();
+    let mut image = Grid::filled(size, size, '.');
+    for (row, col) in image.find_all(|_| true).collect::<Vec<Pos>>() {
+        if !empty_rows[row] && !empty_cols[col] && rng.one_in(20) {
+            image[(row, col)] = '#';
+        }
+    }
+    image.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use aoc_core::{check, generate::Rng, Day};
+
+    use super::*;
+
+    #[test]
+    fn test_factors() {
+        let input = fs::read_to_string("./input.dev.example1.txt").expect("Cannot read file");
+        let galaxies = parse_input(&input).unwrap();
+        let factor = |factor| NonZeroU64::new(factor).unwrap();
+        assert_eq!(distances_pairs(&galaxies, factor(10)), Ok(1030));
+        assert_eq!(distances_pairs(&galaxies, factor(100)), Ok(8410));
+        assert_eq!(distances::<u64>(&galaxies, NonZeroU64::MIN), Ok(292));
+        assert_eq!(distances::<u64>(&galaxies, NonZeroU64::MAX), Err(Overflow));
+        assert!(distances::<u128>(&galaxies, NonZeroU64::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 80);
+        assert_eq!(input, generate(&mut Rng::new(1), 80));
+        assert!(parse_input(&input).unwrap().len() > 100);
+        check::agree(&Day::of::<Day11>(), &input).unwrap();
+    }
+}
@@ -0,0 +1,3 @@
+fn main() {
+    aoc_core::run::<day11::Day11>("./input.txt");
+}
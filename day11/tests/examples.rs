@@ -0,0 +1,28 @@
+//! The examples of the puzzle description. Part 2 gives no answer for its
+//! factor of a million, only the distances for factors of 10 and 100.
+
+use std::num::NonZeroU64;
+
+use aoc_core::{example::assert_examples, Day, Example, Part};
+use day11::{distances, parse_input};
+
+const EXAMPLES: &[Example] = &[Example {
+    part: Part::One,
+    file: "input.dev.example1.txt",
+    answer: "374",
+}];
+
+#[test]
+fn test_examples() {
+    assert_examples(&Day::of::<day11::Day11>(), EXAMPLES);
+}
+
+#[test]
+fn test_expansion_factors() {
+    let input = std::fs::read_to_string("./input.dev.example1.txt").expect("Cannot read file");
+    let galaxies = parse_input(&input).unwrap();
+    for (factor, answer) in [(10, 1030), (100, 8410)] {
+        let factor = NonZeroU64::new(factor).unwrap();
+        assert_eq!(distances::<u64>(&galaxies, factor), Ok(answer));
+    }
+}
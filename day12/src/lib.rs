@@ -0,0 +1,592 @@
+use std::collections::{BTreeSet, VecDeque};
+
+use aoc_core::{
+    generate::Rng,
+    memo::Memo,
+    num::{Answer, Int, Overflow, Total},
+    parse::{parse_lines, unsigned, IResult},
+    Alternative, ParseError, Part, Solution, Streaming,
+};
+
+use nom::{
+    bytes::complete::is_a,
+    character::complete::char,
+    combinator::{cut, map, verify},
+    error::context,
+    multi::separated_list1,
+    sequence::separated_pair,
+};
+use rayon::prelude::*;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Input = Vec<Record>;
+    type Answer1 = Result<u64, Overflow>;
+    type Answer2 = Result<u64, Overflow>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64, Overflow> {
+        part1_memo(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64, Overflow> {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![
+            Alternative {
+                part: Part::One,
+                name: "part1_backtrack",
+                solve: |input| part1_backtrack(input).answer(),
+            },
+            Alternative {
+                part: Part::One,
+                name: "part1_parallel",
+                solve: |input| part1_parallel(input).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_parallel",
+                solve: |input| part2_parallel(input).answer(),
+            },
+            Alternative {
+                part: Part::One,
+                name: "part1_wide",
+                solve: |input| part1_memo::<u128>(input).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_wide",
+                solve: |input| part2::<u128>(input).answer(),
+            },
+        ]
+    }
+}
+
+impl Streaming for Day12 {
+    type State = (Total<u64>, Total<u64>);
+
+    fn feed(state: &mut Self::State, record: &str) -> Result<(), ParseError> {
+        let parsed = parse_input(record)?;
+        state.0.add(part1_memo(&parsed));
+        state.1.add(part2(&parsed));
+        Ok(())
+    }
+
+    fn finish(state: Self::State) -> Result<(Self::Answer1, Self::Answer2), ParseError> {
+        Ok((state.0.get(), state.1.get()))
+    }
+}
+
+/// A row of springs and the sizes of its contiguous damaged groups.
+pub type Record = (String, VecDeque<u64>);
+
+trait Mergable {
+    fn merge(&self) -> Vec<Vec<u64>>;
+}
+
+impl Mergable for Vec<u64> {
+    fn merge(&self) -> Vec<Vec<u64>> {
+        if self.is_empty() {
+            return vec![];
+        }
+        let mut return_vec: Vec<Vec<u64>> = vec![];
+        let mut accumulator: Vec<u64> = vec![self[0]];
+        for i in 1..self.len() {
+            if self[i - 1] + 1 == self[i] {
+                accumulator.push(self[i]);
+            } else {
+                return_vec.push(accumulator);
+                accumulator = vec![self[i]];
+            }
+        }
+        if !accumulator.is_empty() {
+            return_vec.push(accumulator);
+        }
+        return_vec
+    }
+}
+pub fn parse_input(input: &str) -> Result<Vec<Record>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let err =
+                |rest: &str, expected: &str| ParseError::at(Day12::DAY, input, rest, expected);
+            let (spring, conditions) = line.split_at(line.find(' ').unwrap_or(line.len()));
+            if let Some(indx) = spring.find(|c| !matches!(c, '.' | '#' | '?')) {
+                return Err(err(&spring[indx..], "`.`, `#` or `?`"));
+            }
+            if spring.is_empty() {
+                return Err(err(spring, "a row of springs"));
+            }
+            let Some(conditions) = conditions.strip_prefix(' ') else {
+                return Err(err(conditions, "a space and group sizes"));
+            };
+            let conditions = conditions
+                .split(',')
+                .map(|elem| match elem.parse::<u64>() {
+                    Ok(size) if size > 0 => Ok(size),
+                    _ => Err(err(elem, "a group size")),
+                })
+                .collect::<Result<VecDeque<u64>, ParseError>>()?;
+            Ok((spring.to_owned(), conditions))
+        })
+        .collect::<Result<Vec<Record>, ParseError>>()
+}
+
+/// `???.### 1,1,3`
+fn parse_record(line: &str) -> IResult<'_, Record> {
+    let springs = context("`.`, `#` or `?`", is_a(".#?"));
+    let group = context("a group size", verify(unsigned, |&size: &u64| size > 0));
+    map(
+        separated_pair(
+            springs,
+            context("` `", char(' ')),
+            separated_list1(char(','), cut(group)),
+        ),
+        |(springs, groups)| (springs.to_owned(), groups.into()),
+    )(line)
+}
+
+/// Same as [`parse_input`], built from nom parsers.
+pub fn parse_input_nom(input: &str) -> Result<Vec<Record>, ParseError> {
+    parse_lines(Day12::DAY, input, parse_record)
+}
+
+pub fn unfold(records: &[Record]) -> Vec<Record> {
+    records
+        .iter()
+        .map(|(spring, conditions)| {
+            let mut accum_string: String = "".to_string();
+            let mut accum_vec: VecDeque<u64> = VecDeque::new();
+            for i in 0..5 {
+                accum_string += spring;
+                if i != 4 {
+                    accum_string += "?";
+                }
+            }
+            for _ in 0..5 {
+                for &condition in conditions.iter() {
+                    accum_vec.push_back(condition);
+                }
+            }
+
+            (accum_string, accum_vec)
+        })
+        .collect::<Vec<Record>>()
+}
+
+fn backtrack_helper(
+    current_state: &mut Vec<char>,
+    q_indexes: &mut Vec<usize>,
+    seen_indexes: &mut BTreeSet<usize>,
+    seen: &mut BTreeSet<String>,
+) {
+    if seen_indexes.len() == q_indexes.len() {
+        return;
+    }
+    for q_index in q_indexes.clone() {
+        if !seen_indexes.contains(&q_index) {
+            current_state[q_index] = '#';
+            seen_indexes.insert(q_index);
+            let collected_string = current_state.iter().collect::<String>();
+            seen.insert(collected_string);
+            backtrack_helper(current_state, q_indexes, seen_indexes, seen);
+            seen_indexes.remove(&q_index);
+            current_state[q_index] = '?';
+        }
+    }
+}
+
+pub fn part1_backtrack(parsed: &[Record]) -> Result<u64, Overflow> {
+    parsed
+        .iter()
+        .try_fold(0, |acc: u64, (current_state, condition)| {
+            let mut q_indexes = current_state
+                .chars()
+                .enumerate()
+                .filter_map(|(indx, character)| {
+                    if character == '?' {
+                        return Some(indx);
+                    }
+                    None
+                })
+                .collect::<Vec<usize>>();
+            let mut seen_indices: BTreeSet<usize> = BTreeSet::new();
+            // Leaving every `?` operational is an arrangement too.
+            let mut seen: BTreeSet<String> = BTreeSet::from([current_state.clone()]);
+            backtrack_helper(
+                &mut current_state.chars().collect::<Vec<char>>(),
+                &mut q_indexes,
+                &mut seen_indices,
+                &mut seen,
+            );
+            let a = seen
+                .iter()
+                .map(|seen_string| {
+                    seen_string
+                        .chars()
+                        .map(|character| {
+                            if character == '?' {
+                                return '.';
+                            }
+                            character
+                        })
+                        .collect::<String>()
+                })
+                .filter(|string| {
+                    let merged = string
+                        .chars()
+                        .enumerate()
+                        .filter_map(|(indx, character)| {
+                            if character == '#' {
+                                return Some(indx as u64);
+                            }
+                            None
+                        })
+                        .collect::<Vec<u64>>()
+                        .merge();
+                    if merged.len() != condition.len() {
+                        return false;
+                    }
+                    merged
+                        .iter()
+                        .map(|vector| vector.len())
+                        .zip(condition.iter())
+                        .all(|(a, &b)| a as u64 == b)
+                });
+
+            let b = a.count() as u64;
+            acc.try_add(b)
+        })
+}
+
+/// The number of arrangements of `spring` from `start_indx` on with the
+/// groups of `condq` still to place. The groups left are always the last
+/// ones of the record, so their number tells them apart.
+fn recurse<N: Int>(
+    start_indx: usize,
+    condq: &mut VecDeque<u64>,
+    spring: &str,
+    memo: &mut Memo<(usize, usize), Result<N, Overflow>>,
+) -> Result<N, Overflow> {
+    memo.get_or_insert_with((start_indx, condq.len()), |memo| {
+        place(start_indx, condq, spring, memo)
+    })
+}
+
+fn place<N: Int>(
+    start_indx: usize,
+    condq: &mut VecDeque<u64>,
+    spring: &str,
+    memo: &mut Memo<(usize, usize), Result<N, Overflow>>,
+) -> Result<N, Overflow> {
+    if condq.is_empty() && start_indx > spring.len() - 1 {
+        return Ok(N::ONE);
+    }
+
+    if condq.is_empty() {
+        if spring[start_indx..].chars().all(|x| x != '#') {
+            return Ok(N::ONE);
+        }
+        return Ok(N::ZERO);
+    }
+
+    if start_indx > spring.len() - 1 {
+        return Ok(N::ZERO);
+    }
+
+    match spring.chars().nth(start_indx).unwrap() {
+        '.' => recurse(1 + start_indx, condq, spring, memo),
+        '#' => {
+            let top_of_q = condq.pop_front().unwrap();
+            // TODO: process this contigously instead of doing recursive calls
+            for i in 0..top_of_q {
+                if let Some(a) = spring.chars().nth(start_indx + i as usize) {
+                    match a {
+                        '#' | '?' => {
+                            continue;
+                        }
+                        '.' => {
+                            return Ok(N::ZERO);
+                        }
+                        _ => {
+                            unreachable!();
+                        }
+                    }
+                } else {
+                    return Ok(N::ZERO);
+                }
+            }
+            if let Some(b) = spring.chars().nth(start_indx + top_of_q as usize) {
+                match b {
+                    '#' => {
+                        return Ok(N::ZERO);
+                    }
+                    '?' => {
+                        return recurse(start_indx + top_of_q as usize + 1, condq, spring, memo);
+                    }
+                    '.' => {}
+                    _ => {
+                        unreachable!();
+                    }
+                }
+            }
+            recurse(top_of_q as usize + start_indx, condq, spring, memo)
+        }
+        '?' => {
+            let mut old_condq = condq.clone();
+            let top_of_q = condq.pop_front().unwrap();
+            // TODO: process this contigously instead of doing recursive calls
+            let mut has_seen_h = false;
+            for i in 0..top_of_q {
+                if let Some(a) = spring.chars().nth(start_indx + i as usize) {
+                    match a {
+                        '#' => {
+                            has_seen_h = true;
+                            continue;
+                        }
+                        '?' => {
+                            continue;
+                        }
+                        '.' => {
+                            if !has_seen_h {
+                                return recurse(
+                                    start_indx + i as usize,
+                                    &mut old_condq,
+                                    spring,
+                                    memo,
+                                );
+                            }
+                            return Ok(N::ZERO);
+                        }
+                        _ => {
+                            unreachable!();
+                        }
+                    }
+                } else {
+                    return Ok(N::ZERO);
+                }
+            }
+
+            if let Some(b) = spring.chars().nth(start_indx + top_of_q as usize) {
+                match b {
+                    '#' => {
+                        return recurse(start_indx + 1, &mut old_condq, spring, memo);
+                    }
+                    '?' => {}
+                    _ => {}
+                }
+            }
+            recurse(1 + top_of_q as usize + start_indx, condq, spring, memo)?.try_add(recurse(
+                start_indx + 1,
+                &mut old_condq,
+                spring,
+                memo,
+            )?)
+        }
+        _ => {
+            unreachable!();
+        }
+    }
+}
+
+fn arrangements<N: Int>((current_state, condition): &Record) -> Result<N, Overflow> {
+    recurse(0, &mut condition.clone(), current_state, &mut Memo::new())
+}
+
+pub fn part1_memo<N: Int>(parsed: &[Record]) -> Result<N, Overflow> {
+    N::try_sum(parsed.iter().map(arrangements))
+}
+
+pub fn part2<N: Int>(records: &[Record]) -> Result<N, Overflow> {
+    N::try_sum(unfold(records).iter().map(arrangements))
+}
+
+pub fn part1_parallel(parsed: &[Record]) -> Result<u64, Overflow> {
+    u64::try_sum(parsed.par_iter().map(arrangements).collect::<Vec<_>>())
+}
+
+pub fn part2_parallel(records: &[Record]) -> Result<u64, Overflow> {
+    u64::try_sum(
+        unfold(records)
+            .par_iter()
+            .map(arrangements)
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// `size` rows of 6 to 20 springs with about half of them unknown. Rows with
+/// more than 64 arrangements are drawn again, which keeps the unfolded
+/// counts of part 2 below about 10^12 each.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    let mut rows = 0;
+    while rows < size {
+        let len = rng.range(6..=20) as usize;
+        let (mut springs, mut groups) = (String::new(), vec![]);
+        while springs.len() < len {
+            let dots = rng.range(u64::from(!springs.is_empty())..=3) as usize;
+            springs.push_str(&".".repeat(dots.min(len - springs.len())));
+            if springs.len() == len {
+                break;
+            }
+            let group = rng.range(1..=(len - springs.len()).min(7) as u64);
+            springs.push_str(&"#".repeat(group as usize));
+            groups.push(group);
+        }
+        let springs = springs
+            .chars()
+            .map(|spring| if rng.one_in(2) { '?' } else { spring })
+            .collect::<String>();
+        if arrangements::<u64>(&(springs.clone(), groups.iter().copied().collect()))
+            .is_ok_and(|count| count <= 64)
+        {
+            let groups = groups.iter().map(u64::to_string).collect::<Vec<String>>();
+            input.push_str(&format!("{} {}\n", springs, groups.join(",")));
+            rows += 1;
+        }
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use aoc_core::{check, generate::Rng, stream, trace, Day};
+    use proptest::prelude::*;
+
+    use super::*;
+    #[test]
+    fn test_dev_part1_backtrack() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        assert_eq!(part1_backtrack(&parse_input(&input).unwrap()), Ok(21));
+    }
+
+    #[test]
+    fn test_dev_part1() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        // assert_eq!(
+        //     recurse(
+        //         13,
+        //         &mut vec![1 as u64].into_iter().collect::<VecDeque<u64>>(),
+        //         ".??..??...?##."
+        //     ),
+        //     1
+        // );
+
+        let input2 = "????#.##??###???#?#? 2,3,4,4";
+        let input3 = "????.######..#####. 1,6,5";
+        assert_eq!(part1_memo(&parse_input(&input).unwrap()), Ok(21));
+        assert_eq!(part1_memo(&parse_input(input2).unwrap()), Ok(2));
+        assert_eq!(part1_memo(&parse_input(input3).unwrap()), Ok(4));
+    }
+
+    #[test]
+    fn test_parse_2() {
+        let input = "???.### 1,1,3";
+        assert_eq!(
+            unfold(&parse_input(input).unwrap())[0].0,
+            "???.###????.###????.###????.###????.###"
+        );
+    }
+
+    #[test]
+    fn test_dev_part2() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        assert_eq!(part2(&parse_input(&input).unwrap()), Ok(525152));
+    }
+
+    #[test]
+    fn test_widths() {
+        // Ten single springs among 60 unknowns, C(51, 10) arrangements.
+        let records = parse_input(&format!("{} 1,1,1,1,1,1,1,1,1,1", "?".repeat(60))).unwrap();
+        assert_eq!(part1_memo::<u32>(&records), Err(Overflow));
+        assert_eq!(part1_memo::<u64>(&records), Ok(12_777_711_870));
+    }
+
+    #[test]
+    fn test_nom_parser() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        assert_eq!(parse_input_nom(&input), parse_input(&input));
+        let err = parse_input_nom("#.# 1,1\n.?? 1,0\n").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (2, 7, "a group size")
+        );
+    }
+
+    /// `part1_backtrack` tries every order of filling in the `?`s, so rows
+    /// are kept short.
+    fn records() -> impl Strategy<Value = String> {
+        let record = (
+            prop::collection::vec(prop::sample::select(vec!['.', '#', '?']), 1..9),
+            prop::collection::vec(1..4u64, 1..4),
+        );
+        prop::collection::vec(record, 1..6).prop_map(|records| {
+            records
+                .iter()
+                .map(|(springs, groups)| {
+                    let groups = groups.iter().map(u64::to_string).collect::<Vec<_>>();
+                    format!(
+                        "{} {}\n",
+                        springs.iter().collect::<String>(),
+                        groups.join(",")
+                    )
+                })
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_implementations_agree() {
+        let day = Day::of::<Day12>();
+        check::check_fixtures(&day, Path::new("."));
+        check::cross_check(&day, Path::new("."), records());
+    }
+
+    #[test]
+    fn test_stream() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        let parsed = parse_input(&input).unwrap();
+        assert_eq!(
+            stream::solve::<Day12>(input.as_bytes()).unwrap(),
+            (part1_memo(&parsed), part2(&parsed))
+        );
+    }
+
+    #[test]
+    fn test_trace() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        let records = parse_input(&input).unwrap();
+        let (arrangements, count) = trace::with(trace::Count::default(), || part2::<u64>(&records));
+        assert_eq!(arrangements, Ok(525152));
+        assert_eq!(count.0, [("hit", 109), ("miss", 482)].into());
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 100);
+        assert_eq!(input, generate(&mut Rng::new(1), 100));
+        let records = parse_input(&input).unwrap();
+        assert_eq!(records.len(), 100);
+        assert!(records
+            .iter()
+            .all(|record| arrangements::<u64>(record).is_ok_and(|count| count > 0)));
+        assert_eq!(
+            stream::solve::<Day12>(input.as_bytes()).unwrap(),
+            (part1_memo(&records), part2(&records))
+        );
+    }
+}
@@ -0,0 +1,390 @@
+use std::{error::Error, fmt};
+
+use aoc_core::{
+    generate::Rng,
+    num::{Answer, Int, Overflow, SolveError, Total},
+    stream::{Separator, Streaming},
+    Alternative, Grid, ParseError, Part, Solution,
+};
+
+use rayon::prelude::*;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input = Vec<Grid<char>>;
+    type Answer1 = Result<u64, MirrorError>;
+    type Answer2 = Result<u64, MirrorError>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64, MirrorError> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64, MirrorError> {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![
+            Alternative {
+                part: Part::Two,
+                name: "part2_flip",
+                solve: |input| part2_flip(input).answer(),
+            },
+            Alternative {
+                part: Part::One,
+                name: "part1_parallel",
+                solve: |input| part1_parallel(input).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_parallel",
+                solve: |input| part2_parallel(input).answer(),
+            },
+            Alternative {
+                part: Part::One,
+                name: "part1_wide",
+                solve: |input| part1::<u128>(input).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_wide",
+                solve: |input| part2::<u128>(input).answer(),
+            },
+        ]
+    }
+}
+
+impl Streaming for Day13 {
+    const SEPARATOR: Separator = Separator::Block;
+    type State = (Total<u64, MirrorError>, Total<u64, MirrorError>);
+
+    fn feed(state: &mut Self::State, record: &str) -> Result<(), ParseError> {
+        let parsed = parse_input(record)?;
+        state.0.add(part1(&parsed));
+        state.1.add(part2(&parsed));
+        Ok(())
+    }
+
+    fn finish(state: Self::State) -> Result<(Self::Answer1, Self::Answer2), ParseError> {
+        Ok((state.0.get(), state.1.get()))
+    }
+}
+
+/// The numbers of rows above each horizontal mirror line, the reflection
+/// being off by exactly `smudges` cells.
+fn mirrors(grid: &Grid<char>, smudges: usize) -> impl Iterator<Item = u64> + '_ {
+    (1..grid.rows())
+        .filter(move |&above| {
+            let differences = (0..above)
+                .rev()
+                .zip(above..grid.rows())
+                .map(|(top, bottom)| {
+                    grid.row(top)
+                        .iter()
+                        .zip(grid.row(bottom))
+                        .filter(|(top, bottom)| top != bottom)
+                        .count()
+                })
+                .sum::<usize>();
+            differences == smudges
+        })
+        .map(|above| above as u64)
+}
+
+/// Columns left of each vertical mirror, then 100 times the rows above each
+/// horizontal one.
+fn scores(grid: &Grid<char>, smudges: usize) -> Vec<u64> {
+    let mut scores = mirrors(&grid.transpose(), smudges).collect::<Vec<u64>>();
+    scores.extend(mirrors(grid, smudges).map(|rows| rows * 100));
+    scores
+}
+
+/// Why a part has no answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MirrorError {
+    Overflow,
+    /// A pattern without a mirror, or without a mirror with one smudge for
+    /// part 2.
+    NoMirror {
+        smudges: usize,
+    },
+}
+
+impl fmt::Display for MirrorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MirrorError::Overflow => fmt::Display::fmt(&Overflow, f),
+            MirrorError::NoMirror { smudges: 0 } => write!(f, "a pattern has no mirror"),
+            MirrorError::NoMirror { smudges: 1 } => {
+                write!(f, "a pattern has no mirror with a smudge")
+            }
+            MirrorError::NoMirror { smudges } => {
+                write!(f, "a pattern has no mirror with {} smudges", smudges)
+            }
+        }
+    }
+}
+
+impl Error for MirrorError {}
+
+impl From<Overflow> for MirrorError {
+    fn from(_: Overflow) -> Self {
+        MirrorError::Overflow
+    }
+}
+
+impl From<MirrorError> for SolveError {
+    fn from(err: MirrorError) -> Self {
+        match err {
+            MirrorError::Overflow => SolveError::Overflow,
+            MirrorError::NoMirror { .. } => SolveError::NoAnswer(err.to_string()),
+        }
+    }
+}
+
+fn score<N: Int>(grid: &Grid<char>, smudges: usize) -> Result<N, MirrorError> {
+    let score = scores(grid, smudges)
+        .first()
+        .copied()
+        .ok_or(MirrorError::NoMirror { smudges })?;
+    Ok(N::of(score)?)
+}
+
+/// The patterns of `input`, which are separated by blank lines, as slices
+/// of it so that errors point into it. Lines may end in `\r\n`.
+fn patterns(input: &str) -> Vec<&str> {
+    let mut patterns = vec![];
+    let mut start = 0;
+    for (indx, _) in input.match_indices('\n') {
+        let after = &input[indx + 1..];
+        let blank = if after.starts_with('\n') {
+            1
+        } else if after.starts_with("\r\n") {
+            2
+        } else {
+            continue;
+        };
+        if indx < start {
+            continue;
+        }
+        patterns.push(input[start..indx].trim_end_matches('\r'));
+        start = indx + 1 + blank;
+    }
+    patterns.push(&input[start..]);
+    patterns
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Grid<char>>, ParseError> {
+    patterns(input)
+        .into_iter()
+        .map(|pattern| {
+            Grid::parse_block(Day13::DAY, input, pattern, "`.` or `#`", |c| {
+                matches!(c, '.' | '#').then_some(c)
+            })
+        })
+        .collect()
+}
+
+pub fn part1<N: Int>(parsed: &[Grid<char>]) -> Result<N, MirrorError> {
+    N::try_sum(parsed.iter().map(|grid| score(grid, 0)))
+}
+
+pub fn part2<N: Int>(parsed: &[Grid<char>]) -> Result<N, MirrorError> {
+    N::try_sum(parsed.iter().map(|grid| score(grid, 1)))
+}
+
+pub fn part1_parallel(parsed: &[Grid<char>]) -> Result<u64, MirrorError> {
+    u64::try_sum(
+        parsed
+            .par_iter()
+            .map(|grid| score(grid, 0))
+            .collect::<Vec<_>>(),
+    )
+}
+
+pub fn part2_parallel(parsed: &[Grid<char>]) -> Result<u64, MirrorError> {
+    u64::try_sum(
+        parsed
+            .par_iter()
+            .map(|grid| score(grid, 1))
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Reference for part 2: cleans each cell in turn until a mirror other than
+/// the original ones shows up.
+pub fn part2_flip(parsed: &[Grid<char>]) -> Result<u64, MirrorError> {
+    u64::try_sum(parsed.iter().map(|grid| {
+        let original = scores(grid, 0);
+        grid.iter()
+            .find_map(|(pos, _)| {
+                let mut cleaned = grid.clone();
+                cleaned[pos] = if grid[pos] == '#' { '.' } else { '#' };
+                scores(&cleaned, 0)
+                    .into_iter()
+                    .find(|score| !original.contains(score))
+            })
+            .ok_or(MirrorError::NoMirror { smudges: 1 })
+    }))
+}
+
+/// `size` patterns of 5 to 17 by 7 to 17 cells, each with one mirror and one
+/// mirror with a smudge. The two mirror lines reflect disjoint bands of
+/// rows, half of the patterns are then transposed.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut patterns = vec![];
+    while patterns.len() < size {
+        let (rows, cols) = (rng.range(7..=17) as usize, rng.range(5..=17) as usize);
+        let mirror = rng.range(1..=(rows as u64 - 2) / 2) as usize;
+        let smudged = rng.range((2 * mirror + rows).div_ceil(2) as u64..=rows as u64 - 1) as usize;
+        let cells = (0..rows * cols)
+            .map(|_| *rng.pick(&['.', '#']))
+            .collect::<Vec<char>>();
+        let mut grid = Grid::new(rows, cols, cells);
+        for row in mirror..2 * mirror {
+            let mirrored = grid.row(2 * mirror - 1 - row).to_vec();
+            grid.row_mut(row).copy_from_slice(&mirrored);
+        }
+        for row in smudged..rows {
+            let mirrored = grid.row(2 * smudged - 1 - row).to_vec();
+            grid.row_mut(row).copy_from_slice(&mirrored);
+        }
+        let smudge = &mut grid.row_mut(rng.range(smudged as u64..=rows as u64 - 1) as usize)
+            [rng.below(cols as u64) as usize];
+        *smudge = if *smudge == '#' { '.' } else { '#' };
+        if rng.one_in(2) {
+            grid = grid.transpose();
+        }
+        if scores(&grid, 0).len() == 1 && scores(&grid, 1).len() == 1 {
+            patterns.push(grid.to_string());
+        }
+    }
+    patterns.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use aoc_core::{check, generate::Rng, stream, Day};
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_dev_part1() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        assert_eq!(part1(&parse_input(&input).unwrap()), Ok(405));
+    }
+
+    #[test]
+    fn test_dev_part2() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        assert_eq!(part2(&parse_input(&input).unwrap()), Ok(400));
+    }
+
+    #[test]
+    fn test_no_mirror() {
+        let day = Day::of::<Day13>();
+        // No smudge, part 1 still has its answer.
+        let parsed = parse_input("##\n..\n").unwrap();
+        assert_eq!(part1::<u64>(&parsed), Ok(1));
+        assert_eq!(
+            part2::<u64>(&parsed),
+            Err(MirrorError::NoMirror { smudges: 1 })
+        );
+        check::agree(&day, "##\n..\n").unwrap();
+        // Only mirrors with a smudge, part 2 still has its answer.
+        let parsed = parse_input("#.\n##\n").unwrap();
+        assert_eq!(
+            part1::<u64>(&parsed),
+            Err(MirrorError::NoMirror { smudges: 0 })
+        );
+        assert_eq!(part2::<u64>(&parsed), Ok(1));
+        check::agree(&day, "#.\n##\n").unwrap();
+    }
+
+    #[test]
+    fn test_crlf() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        let crlf = input.replace('\n', "\r\n");
+        assert_eq!(parse_input(&crlf), parse_input(&input));
+        let err = parse_input("#.\r\n#.\r\n\r\n#.\r\n#x\r\n").unwrap_err();
+        assert_eq!((err.line, err.column), (5, 2));
+    }
+
+    /// A random pattern mirrored around a random line, kept only if, as in
+    /// the puzzle, it has one mirror and one mirror with a smudge.
+    fn pattern() -> impl Strategy<Value = String> {
+        (2..8usize, 2..8usize)
+            .prop_flat_map(|(rows, cols)| {
+                (
+                    prop::collection::vec(prop::bool::ANY, rows * cols),
+                    Just((rows, cols)),
+                    1..rows,
+                    prop::bool::ANY,
+                )
+            })
+            .prop_map(|(cells, (rows, cols), above, transpose)| {
+                let cells = cells
+                    .into_iter()
+                    .map(|ash| if ash { '.' } else { '#' })
+                    .collect();
+                let mut grid = Grid::new(rows, cols, cells);
+                for row in above..rows.min(2 * above) {
+                    let mirrored = grid.row(2 * above - 1 - row).to_vec();
+                    grid.row_mut(row).copy_from_slice(&mirrored);
+                }
+                if transpose {
+                    grid = grid.transpose();
+                }
+                grid
+            })
+            .prop_filter("one mirror and one smudged mirror", |grid| {
+                scores(grid, 0).len() == 1 && scores(grid, 1).len() == 1
+            })
+            .prop_map(|grid| grid.to_string())
+    }
+
+    #[test]
+    fn test_implementations_agree() {
+        let day = Day::of::<Day13>();
+        let patterns =
+            prop::collection::vec(pattern(), 1..4).prop_map(|patterns| patterns.join("\n"));
+        check::check_fixtures(&day, Path::new("."));
+        check::cross_check(&day, Path::new("."), patterns);
+    }
+
+    #[test]
+    fn test_stream() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        let parsed = parse_input(&input).unwrap();
+        assert_eq!(
+            stream::solve::<Day13>(input.as_bytes()).unwrap(),
+            (part1(&parsed), part2(&parsed))
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 100);
+        assert_eq!(input, generate(&mut Rng::new(1), 100));
+        let patterns = parse_input(&input).unwrap();
+        assert_eq!(patterns.len(), 100);
+        assert_eq!(part2_flip(&patterns), part2(&patterns));
+        assert_eq!(
+            stream::solve::<Day13>(input.as_bytes()).unwrap(),
+            (part1(&patterns), part2(&patterns))
+        );
+    }
+}
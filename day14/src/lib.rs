@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use aoc_core::{
+    generate::Rng,
+    grid::{DOWN, LEFT, RIGHT, UP},
+    num::Answer,
+    Alternative, Grid, ParseError, Part, Solution,
+};
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = Grid<char>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> u64 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> u64 {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![
+            Alternative {
+                part: Part::One,
+                name: "part1_scan",
+                solve: |input| part1_scan(input).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_floyd",
+                solve: |input| part2_floyd(input).answer(),
+            },
+        ]
+    }
+}
+
+/// Spin cycles asked for by part 2.
+const CYCLES: usize = 1_000_000_000;
+
+pub fn parse_input(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse(Day14::DAY, input, "`O`, `#` or `.`", |c| {
+        matches!(c, 'O' | '#' | '.').then_some(c)
+    })
+}
+
+/// Rolls every round rock as far as it goes towards `direction`, one of the
+/// orthogonal offsets.
+pub fn tilt(grid: &mut Grid<char>, direction: (isize, isize)) {
+    let (rows, cols) = (grid.rows(), grid.cols());
+    let (lines, len) = if direction.0 == 0 {
+        (rows, cols)
+    } else {
+        (cols, rows)
+    };
+    for line in 0..lines {
+        // Positions along the line, from the edge the rocks roll towards.
+        let at = |indx: usize| match direction {
+            UP => (indx, line),
+            DOWN => (rows - 1 - indx, line),
+            LEFT => (line, indx),
+            RIGHT => (line, cols - 1 - indx),
+            _ => panic!("not an orthogonal direction: {:?}", direction),
+        };
+        let mut free = 0;
+        for indx in 0..len {
+            match grid[at(indx)] {
+                '#' => free = indx + 1,
+                'O' => {
+                    grid[at(indx)] = '.';
+                    grid[at(free)] = 'O';
+                    free += 1;
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Tilts north, west, south, then east.
+pub fn spin(grid: &mut Grid<char>) {
+    for direction in [UP, LEFT, DOWN, RIGHT] {
+        tilt(grid, direction);
+    }
+}
+
+/// The load on the north support beams: each round rock weighs as many as
+/// there are rows from it to the south edge.
+pub fn load(grid: &Grid<char>) -> u64 {
+    grid.find_all(|&c| c == 'O')
+        .map(|(row, _)| (grid.rows() - row) as u64)
+        .sum()
+}
+
+pub fn part1(grid: &Grid<char>) -> u64 {
+    let mut tilted = grid.clone();
+    tilt(&mut tilted, UP);
+    load(&tilted)
+}
+
+/// Same as [`part1`] without moving the rocks: each one ends up below the
+/// last cube rock or rolled rock of its column.
+pub fn part1_scan(grid: &Grid<char>) -> u64 {
+    let mut free = vec![0; grid.cols()];
+    let mut load = 0;
+    for ((row, col), &c) in grid.iter() {
+        match c {
+            '#' => free[col] = row + 1,
+            'O' => {
+                load += (grid.rows() - free[col]) as u64;
+                free[col] += 1;
+            }
+            _ => {}
+        }
+    }
+    load
+}
+
+/// The platform after `cycles` spins. The states seen are kept by content,
+/// once one comes back the rest of the cycles repeat the states since.
+pub fn spin_cycles(grid: &Grid<char>, cycles: usize) -> Grid<char> {
+    let mut seen: HashMap<Grid<char>, usize> = HashMap::new();
+    let mut states = vec![];
+    let mut grid = grid.clone();
+    for cycle in 0..cycles {
+        if let Some(&first) = seen.get(&grid) {
+            return states.swap_remove(first + (cycles - first) % (cycle - first));
+        }
+        seen.insert(grid.clone(), cycle);
+        states.push(grid.clone());
+        spin(&mut grid);
+    }
+    grid
+}
+
+pub fn part2(grid: &Grid<char>) -> u64 {
+    load(&spin_cycles(grid, CYCLES))
+}
+
+/// Same as [`part2`] finding the repeat with Floyd's tortoise and hare, which
+/// only ever keeps two states.
+pub fn part2_floyd(grid: &Grid<char>) -> u64 {
+    let spun = |grid: &Grid<char>| {
+        let mut next = grid.clone();
+        spin(&mut next);
+        next
+    };
+    let mut tortoise = spun(grid);
+    let mut hare = spun(&tortoise);
+    while tortoise != hare {
+        tortoise = spun(&tortoise);
+        hare = spun(&spun(&hare));
+    }
+    let mut first = 0;
+    tortoise = grid.clone();
+    while tortoise != hare {
+        tortoise = spun(&tortoise);
+        hare = spun(&hare);
+        first += 1;
+    }
+    let mut period = 1;
+    hare = spun(&tortoise);
+    while tortoise != hare {
+        hare = spun(&hare);
+        period += 1;
+    }
+    for _ in 0..(CYCLES - first) % period {
+        tortoise = spun(&tortoise);
+    }
+    load(&tortoise)
+}
+
+/// A square platform of `size` rows, a tenth of it cube rocks and a fifth
+/// round ones.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let cells = (0..size * size)
+        .map(|_| match rng.below(10) {
+            0 => '#',
+            1 | 2 => 'O',
+            _ => '.',
+        })
+        .collect();
+    Grid::new(size, size, cells).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use aoc_core::{check, generate::Rng, Day};
+
+    use super::*;
+
+    #[test]
+    fn test_spin() {
+        let input = fs::read_to_string("./input.dev.example1.txt").expect("Cannot read file");
+        let mut grid = parse_input(&input).unwrap();
+        spin(&mut grid);
+        let after = ".....#....\n....#...O#\n...OO##...\n.OO#......\n.....OOO#.\n\
+                     .O#...O#.#\n....O#....\n......OOOO\n#...O###..\n#..OO#....\n";
+        assert_eq!(grid.to_string(), after);
+        let grid = parse_input(&input).unwrap();
+        assert_eq!(spin_cycles(&grid, 1).to_string(), after);
+        assert_eq!(load(&spin_cycles(&grid, 1_000)), 64);
+    }
+
+    #[test]
+    fn test_tilt() {
+        let mut grid = parse_input("O.#O.\n.O..O\n").unwrap();
+        tilt(&mut grid, RIGHT);
+        assert_eq!(grid.to_string(), ".O#.O\n...OO\n");
+        tilt(&mut grid, DOWN);
+        assert_eq!(grid.to_string(), "..#.O\n.O.OO\n");
+        tilt(&mut grid, LEFT);
+        assert_eq!(grid.to_string(), "..#O.\nOOO..\n");
+        tilt(&mut grid, UP);
+        assert_eq!(grid.to_string(), "OO#O.\n..O..\n");
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 30);
+        assert_eq!(input, generate(&mut Rng::new(1), 30));
+        check::agree(&Day::of::<Day14>(), &input).unwrap();
+    }
+}
@@ -0,0 +1,3 @@
+fn main() {
+    aoc_core::run::<day14::Day14>("./input.txt");
+}
@@ -0,0 +1,21 @@
+//! Generated by `aoc examples --day 14` from the puzzle description.
+
+use aoc_core::{example::assert_examples, Day, Example, Part};
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: Part::One,
+        file: "input.dev.example1.txt",
+        answer: "136",
+    },
+    Example {
+        part: Part::Two,
+        file: "input.dev.example1.txt",
+        answer: "64",
+    },
+];
+
+#[test]
+fn test_examples() {
+    assert_examples(&Day::of::<day14::Day14>(), EXAMPLES);
+}
@@ -0,0 +1,413 @@
+use aoc_core::{
+    generate::Rng,
+    num::{Answer, Int, Overflow, Total},
+    parse::{parse_all, IResult},
+    stream::{Separator, Streaming},
+    trace::{self, Event},
+    Alternative, ParseError, Part, Solution,
+};
+
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while1},
+    character::complete::{char, digit1},
+    combinator::{cut, map, map_res, recognize},
+    error::context,
+    multi::separated_list1,
+    sequence::{pair, preceded},
+};
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Input = Vec<String>;
+    type Answer1 = Result<u64, Overflow>;
+    type Answer2 = Result<u64, Overflow>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_steps(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64, Overflow> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64, Overflow> {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![
+            Alternative {
+                part: Part::One,
+                name: "part1_wide",
+                solve: |input| part1::<u128>(input).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_wide",
+                solve: |input| part2::<u128>(input).answer(),
+            },
+        ]
+    }
+}
+
+/// The steps are read one at a time, only the hash sum and the lenses are
+/// kept.
+impl Streaming for Day15 {
+    const SEPARATOR: Separator = Separator::Byte(b',');
+    type State = (Total<u64>, Boxes);
+
+    fn feed((sum, boxes): &mut Self::State, record: &str) -> Result<(), ParseError> {
+        for step in parse_steps(record)? {
+            sum.add(Ok(hash(&step)));
+            boxes.apply(&step);
+        }
+        Ok(())
+    }
+
+    fn finish((sum, boxes): Self::State) -> Result<(Self::Answer1, Self::Answer2), ParseError> {
+        Ok((sum.get(), boxes.focusing_power()))
+    }
+}
+
+/// `label=focal length` or `label-`
+fn check_step(input: &str, step: &str) -> Result<(), ParseError> {
+    let err = |rest: &str, expected: &str| ParseError::at(Day15::DAY, input, rest, expected);
+    let label_len = step
+        .find(|c: char| !c.is_ascii_lowercase())
+        .unwrap_or(step.len());
+    if label_len == 0 {
+        return Err(err(step, "a label"));
+    }
+    let rest = &step[label_len..];
+    if let Some(focal_length) = rest.strip_prefix('=') {
+        return match focal_length.find(|c: char| !c.is_ascii_digit()) {
+            _ if focal_length.is_empty() => Err(err(focal_length, "a focal length")),
+            Some(indx) => Err(err(&focal_length[indx..], "a digit or `,`")),
+            None => match focal_length.parse::<u64>() {
+                Ok(_) => Ok(()),
+                Err(_) => Err(err(focal_length, "a focal length")),
+            },
+        };
+    }
+    match rest.strip_prefix('-') {
+        Some("") => Ok(()),
+        Some(rest) => Err(err(rest, "`,`")),
+        None => Err(err(rest, "a letter, `=` or `-`")),
+    }
+}
+
+/// The comma separated steps, anything printable can be hashed.
+pub fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {
+    input
+        .trim_end_matches('\n')
+        .split(',')
+        .map(|step| {
+            if step.is_empty() {
+                return Err(ParseError::at(Day15::DAY, input, step, "a step"));
+            }
+            match step.find(|c: char| !c.is_ascii_graphic()) {
+                Some(indx) => Err(ParseError::at(
+                    Day15::DAY,
+                    input,
+                    &step[indx..],
+                    "a printable character or `,`",
+                )),
+                None => Ok(step.to_string()),
+            }
+        })
+        .collect()
+}
+
+/// Like `parse_input`, but every step must also be an operation on a lens.
+pub fn parse_steps(input: &str) -> Result<Vec<String>, ParseError> {
+    let steps = parse_input(input)?;
+    input
+        .trim_end_matches('\n')
+        .split(',')
+        .try_for_each(|step| check_step(input, step))?;
+    Ok(steps)
+}
+
+fn parse_step(input: &str) -> IResult<'_, &str> {
+    let label = context("a label", take_while1(|c: char| c.is_ascii_lowercase()));
+    let operation = context(
+        "a letter, `=` or `-`",
+        alt((
+            recognize(preceded(
+                char('='),
+                cut(context(
+                    "a focal length",
+                    map_res(digit1, str::parse::<u64>),
+                )),
+            )),
+            tag("-"),
+        )),
+    );
+    recognize(pair(label, operation))(input)
+}
+
+/// Same as [`parse_steps`], built from nom parsers.
+pub fn parse_steps_nom(input: &str) -> Result<Vec<String>, ParseError> {
+    parse_all(
+        Day15::DAY,
+        input,
+        separated_list1(char(','), cut(map(parse_step, str::to_string))),
+    )
+}
+
+/// The HASH algorithm, line breaks are ignored.
+fn hash(step: &str) -> u64 {
+    step.chars().fold(0, |accum, x| {
+        if x == '\n' {
+            return accum;
+        }
+        let mut hash = accum + x as u64;
+        hash *= 17;
+        hash %= 256;
+        hash
+    })
+}
+
+pub fn part1<N: Int>(steps: &[String]) -> Result<N, Overflow> {
+    N::try_sum(steps.iter().map(|step| N::of(hash(step))))
+}
+
+/// A step of the initialization sequence and the lenses in its box after it.
+#[derive(Debug)]
+pub struct Step<'a> {
+    pub step: &'a str,
+    pub box_number: u64,
+    pub lenses: &'a [(String, u64)],
+}
+
+impl Event for Step<'_> {
+    fn name(&self) -> &'static str {
+        "step"
+    }
+}
+
+/// The lenses in each of the 256 boxes, as label and focal length.
+#[derive(Debug)]
+pub struct Boxes(Vec<Vec<(String, u64)>>);
+
+impl Default for Boxes {
+    fn default() -> Self {
+        Boxes(vec![Vec::new(); 256])
+    }
+}
+
+impl Boxes {
+    /// Puts in or replaces a lens for `label=focal length`, takes one out for
+    /// `label-`.
+    fn apply(&mut self, step: &str) {
+        let (key, value) = if step.contains('=') {
+            let split = step.split('=').collect::<Vec<&str>>();
+            let focal_length = split[1].parse::<u64>();
+            (
+                split[0],
+                Some(focal_length.expect("focal length checked by the parser")),
+            )
+        } else {
+            (step.split('-').next().unwrap(), None)
+        };
+        let box_number = hash(key);
+        let lenses = &mut self.0[box_number as usize];
+        match value {
+            Some(value) => match lenses.iter_mut().find(|x| x.0 == key) {
+                Some(x) => x.1 = value,
+                None => lenses.push((key.to_string(), value)),
+            },
+            None => lenses.retain(|x| x.0 != key),
+        }
+        trace::emit(|| Step {
+            step,
+            box_number,
+            lenses,
+        });
+    }
+
+    fn focusing_power<N: Int>(&self) -> Result<N, Overflow> {
+        N::try_sum(self.0.iter().enumerate().flat_map(|(box_indx, val)| {
+            val.iter().enumerate().map(move |(indx, val)| {
+                N::of((box_indx + 1) as u64)?
+                    .try_mul(N::of((indx + 1) as u64)?)?
+                    .try_mul(N::of(val.1)?)
+            })
+        }))
+    }
+}
+
+pub fn part2<N: Int>(steps: &[String]) -> Result<N, Overflow> {
+    let mut boxes = Boxes::default();
+    steps.iter().for_each(|step| boxes.apply(step));
+    boxes.focusing_power()
+}
+
+/// `size` steps on a quarter as many labels of two to six letters, two thirds
+/// of them inserting a lens.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let labels = (0..size.div_ceil(4))
+        .map(|_| {
+            (0..rng.range(2..=6))
+                .map(|_| char::from(b'a' + rng.below(26) as u8))
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>();
+    let steps = (0..size)
+        .map(|_| {
+            let label = rng.pick(&labels);
+            match rng.one_in(3) {
+                true => format!("{}-", label),
+                false => format!("{}={}", label, rng.range(1..=9)),
+            }
+        })
+        .collect::<Vec<String>>();
+    format!("{}\n", steps.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use aoc_core::stream;
+
+    use super::*;
+
+    #[test]
+    fn test_basic_hash() {
+        assert_eq!(part1(&parse_input("HASH").unwrap()), Ok(52));
+    }
+
+    #[test]
+    fn test_basic_hash2() {
+        assert_eq!(part1(&parse_input("ot=7").unwrap()), Ok(231));
+    }
+
+    #[test]
+    fn test_dev_part1() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        assert_eq!(part1(&parse_input(&input).unwrap()), Ok(1320));
+    }
+
+    #[test]
+    fn test_dev_part2() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        assert_eq!(part2(&parse_steps(&input).unwrap()), Ok(145));
+    }
+
+    #[test]
+    fn test_widths() {
+        // `a` goes in box 113.
+        let steps = parse_steps("a=40000000").unwrap();
+        assert_eq!(part2::<u32>(&steps), Err(Overflow));
+        assert_eq!(part2::<u64>(&steps), Ok(4_560_000_000));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("rn=1,cm-,qp=3\r\n").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 14, "a printable character or `,`")
+        );
+        let err = parse_steps("rn=1,cm-,qp=3x").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 14, "a digit or `,`")
+        );
+        let err = parse_steps("rn=1,=2,qp=3").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 6, "a label")
+        );
+        let err = parse_steps("rn=1,cm=99999999999999999999").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 9, "a focal length")
+        );
+    }
+
+    #[test]
+    fn test_nom_parser() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        assert_eq!(parse_steps_nom(&input), parse_steps(&input));
+        let err = parse_steps_nom("rn=1,=2,qp=3").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 6, "a label")
+        );
+        let err = parse_steps_nom("rn=1,cm=99999999999999999999").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 9, "a focal length")
+        );
+    }
+
+    #[test]
+    fn test_stream() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        let parsed = parse_steps(&input).unwrap();
+        assert_eq!(
+            stream::solve::<Day15>(input.as_bytes()).unwrap(),
+            (part1(&parsed), part2(&parsed))
+        );
+    }
+
+    #[test]
+    fn test_stream_error() {
+        for input in [
+            "rn=1,,cm-",
+            "rn=1,cm=x,qp-\n",
+            "rn=1,cm-,qp=99999999999999999999,ab-",
+        ] {
+            let err = parse_steps(input).unwrap_err();
+            let Err(stream::StreamError::Parse(streamed)) =
+                stream::solve::<Day15>(input.as_bytes())
+            else {
+                panic!("expected a parse error on {:?}", input);
+            };
+            assert_eq!(
+                (streamed.line, streamed.column, streamed.snippet),
+                (err.line, err.column, err.snippet),
+                "{:?}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_trace() {
+        let steps = parse_steps("rn=1,cm-,qp=3,cm=2,qp-").unwrap();
+        let (_, print) = trace::with(trace::Print(Vec::new()), || part2::<u64>(&steps));
+        let print = String::from_utf8(print.0).unwrap();
+        assert_eq!(
+            print.lines().collect::<Vec<&str>>(),
+            [
+                "Step { step: \"rn=1\", box_number: 0, lenses: [(\"rn\", 1)] }",
+                "Step { step: \"cm-\", box_number: 0, lenses: [(\"rn\", 1)] }",
+                "Step { step: \"qp=3\", box_number: 1, lenses: [(\"qp\", 3)] }",
+                "Step { step: \"cm=2\", box_number: 0, lenses: [(\"rn\", 1), (\"cm\", 2)] }",
+                "Step { step: \"qp-\", box_number: 1, lenses: [] }",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 1000);
+        assert_eq!(input, generate(&mut Rng::new(1), 1000));
+        let steps = parse_steps(&input).unwrap();
+        assert_eq!(steps.len(), 1000);
+        assert_eq!(
+            stream::solve::<Day15>(input.as_bytes()).unwrap(),
+            (part1(&steps), part2(&steps))
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use std::{collections::HashSet, iter};
+
+use aoc_core::{
+    generate::Rng,
+    grid::{Pos, DOWN, LEFT, ORTHOGONAL, RIGHT, UP},
+    num::Answer,
+    Alternative, Grid, ParseError, Part, Solution,
+};
+
+use rayon::prelude::*;
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    const DAY: u8 = 16;
+    type Input = Grid<char>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> u64 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> u64 {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![
+            Alternative {
+                part: Part::One,
+                name: "part1_set",
+                solve: |input| energized_set(input, (0, 0), RIGHT).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_parallel",
+                solve: |input| part2_parallel(input).answer(),
+            },
+        ]
+    }
+}
+
+const TILES: [char; 5] = ['.', '/', '\\', '|', '-'];
+
+pub fn parse_input(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse(Day16::DAY, input, "`.`, a mirror or a splitter", |c| {
+        TILES.contains(&c).then_some(c)
+    })
+}
+
+/// The directions a beam heading `direction` leaves `tile` in.
+fn turns(tile: char, direction: (isize, isize)) -> impl Iterator<Item = (isize, isize)> {
+    let (row, col) = direction;
+    let (first, second) = match tile {
+        '/' => ((-col, -row), None),
+        '\\' => ((col, row), None),
+        '|' if row == 0 => (UP, Some(DOWN)),
+        '-' if col == 0 => (LEFT, Some(RIGHT)),
+        _ => (direction, None),
+    };
+    iter::once(first).chain(second)
+}
+
+/// The tiles a beam entering `start` heading `direction` lights up. Each tile
+/// keeps the directions beams crossed it in as a bitmask, a beam crossing it
+/// the same way again adds nothing, which ends the loops.
+pub fn energized(grid: &Grid<char>, start: Pos, direction: (isize, isize)) -> u64 {
+    let mut crossed = Grid::filled(grid.rows(), grid.cols(), 0u8);
+    let mut beams = vec![(start, direction)];
+    let mut count = 0;
+    while let Some((pos, direction)) = beams.pop() {
+        let bit = 1 << ORTHOGONAL.iter().position(|&d| d == direction).unwrap();
+        if crossed[pos] & bit != 0 {
+            continue;
+        }
+        count += u64::from(crossed[pos] == 0);
+        crossed[pos] |= bit;
+        for direction in turns(grid[pos], direction) {
+            if let Some(next) = grid.offset(pos, direction) {
+                beams.push((next, direction));
+            }
+        }
+    }
+    count
+}
+
+/// Reference for [`energized`] keeping the beams seen in a set.
+pub fn energized_set(grid: &Grid<char>, start: Pos, direction: (isize, isize)) -> u64 {
+    let mut seen = HashSet::new();
+    let mut beams = vec![(start, direction)];
+    while let Some((pos, direction)) = beams.pop() {
+        if seen.insert((pos, direction)) {
+            beams.extend(
+                turns(grid[pos], direction).filter_map(|direction| {
+                    grid.offset(pos, direction).map(|next| (next, direction))
+                }),
+            );
+        }
+    }
+    seen.iter()
+        .map(|&(pos, _)| pos)
+        .collect::<HashSet<Pos>>()
+        .len() as u64
+}
+
+/// Every tile on the edge, with the direction a beam enters it from outside.
+/// Corner tiles come twice.
+fn entries(grid: &Grid<char>) -> Vec<(Pos, (isize, isize))> {
+    let (rows, cols) = (grid.rows(), grid.cols());
+    let mut entries = vec![];
+    for col in 0..cols {
+        entries.push(((0, col), DOWN));
+        entries.push(((rows - 1, col), UP));
+    }
+    for row in 0..rows {
+        entries.push(((row, 0), RIGHT));
+        entries.push(((row, cols - 1), LEFT));
+    }
+    entries
+}
+
+pub fn part1(grid: &Grid<char>) -> u64 {
+    energized(grid, (0, 0), RIGHT)
+}
+
+pub fn part2(grid: &Grid<char>) -> u64 {
+    entries(grid)
+        .into_iter()
+        .map(|(start, direction)| energized(grid, start, direction))
+        .max()
+        .unwrap_or(0)
+}
+
+pub fn part2_parallel(grid: &Grid<char>) -> u64 {
+    entries(grid)
+        .into_par_iter()
+        .map(|(start, direction)| energized(grid, start, direction))
+        .max()
+        .unwrap_or(0)
+}
+
+/// A square contraption of `size` rows, one tile in five a mirror or a
+/// splitter.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(1);
+    let cells = (0..size * size)
+        .map(|_| {
+            if rng.one_in(5) {
+                *rng.pick(&TILES[1..])
+            } else {
+                '.'
+            }
+        })
+        .collect();
+    Grid::new(size, size, cells).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_core::{check, generate::Rng, Day};
+
+    use super::*;
+
+    #[test]
+    fn test_turns() {
+        let turned = |tile, direction| turns(tile, direction).collect::<Vec<_>>();
+        assert_eq!(turned('/', RIGHT), [UP]);
+        assert_eq!(turned('/', DOWN), [LEFT]);
+        assert_eq!(turned('\\', RIGHT), [DOWN]);
+        assert_eq!(turned('\\', UP), [LEFT]);
+        assert_eq!(turned('|', LEFT), [UP, DOWN]);
+        assert_eq!(turned('|', UP), [UP]);
+        assert_eq!(turned('-', DOWN), [LEFT, RIGHT]);
+    }
+
+    #[test]
+    fn test_loop() {
+        // The beam goes round the mirrors forever.
+        let grid = parse_input("/-\\\n...\n\\./\n").unwrap();
+        assert_eq!(energized(&grid, (1, 0), DOWN), 8);
+        assert_eq!(energized_set(&grid, (1, 0), DOWN), 8);
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 40);
+        assert_eq!(input, generate(&mut Rng::new(1), 40));
+        check::agree(&Day::of::<Day16>(), &input).unwrap();
+    }
+}
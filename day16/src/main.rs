@@ -0,0 +1,3 @@
+fn main() {
+    aoc_core::run::<day16::Day16>("./input.txt");
+}
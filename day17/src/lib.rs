@@ -0,0 +1,247 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap},
+    error::Error,
+    fmt,
+    ops::RangeInclusive,
+};
+
+use aoc_core::{
+    generate::Rng,
+    grid::{Pos, DOWN, LEFT, ORTHOGONAL, RIGHT, UP},
+    num::{Answer, SolveError},
+    Alternative, Grid, ParseError, Part, Solution,
+};
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    const DAY: u8 = 17;
+    type Input = Grid<u8>;
+    type Answer1 = Result<u64, Uncrossable>;
+    type Answer2 = Result<u64, Uncrossable>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64, Uncrossable> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64, Uncrossable> {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![
+            Alternative {
+                part: Part::One,
+                name: "part1_segments",
+                solve: |input| solve(min_heat_loss_segments(input, CRUCIBLE)).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_segments",
+                solve: |input| solve(min_heat_loss_segments(input, ULTRA_CRUCIBLE)).answer(),
+            },
+        ]
+    }
+}
+
+/// How many blocks each kind of crucible goes straight between turns.
+const CRUCIBLE: RangeInclusive<usize> = 1..=3;
+const ULTRA_CRUCIBLE: RangeInclusive<usize> = 4..=10;
+
+/// The smallest square city an ultra crucible can always cross.
+const MIN_SIDE: usize = 5;
+
+pub fn parse_input(input: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::parse(Day17::DAY, input, "a digit from 1 to 9", |c| {
+        c.to_digit(10)
+            .filter(|&loss| loss > 0)
+            .map(|loss| loss as u8)
+    })
+}
+
+/// The crucible cannot get to the bottom right block of the city, which is
+/// too narrow for its runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Uncrossable;
+
+impl fmt::Display for Uncrossable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the crucible cannot cross the city")
+    }
+}
+
+impl Error for Uncrossable {}
+
+impl From<Uncrossable> for SolveError {
+    fn from(err: Uncrossable) -> Self {
+        SolveError::NoAnswer(err.to_string())
+    }
+}
+
+/// The least heat lost on the way from the top left block to the bottom
+/// right one, going straight for a number of blocks in `runs` before each
+/// turn and before stopping. Never reverses. `None` if the rules cannot reach
+/// the end, a city of one block needs no move.
+///
+/// A Dijkstra search over `(block, direction, run length)` states, the
+/// direction being an index into [`ORTHOGONAL`].
+pub fn min_heat_loss(city: &Grid<u8>, runs: RangeInclusive<usize>) -> Option<u64> {
+    let (min_run, max_run) = (*runs.start(), *runs.end());
+    let end = (city.rows() - 1, city.cols() - 1);
+    if end == (0, 0) {
+        return Some(0);
+    }
+    let index = |(row, col): Pos, direction: usize, run: usize| {
+        ((row * city.cols() + col) * 4 + direction) * (max_run + 1) + run
+    };
+    let mut best = vec![u64::MAX; city.rows() * city.cols() * 4 * (max_run + 1)];
+    let mut queue = BinaryHeap::new();
+    // Starts heading right or down, with nothing behind it yet.
+    for direction in [1, 2] {
+        best[index((0, 0), direction, 0)] = 0;
+        queue.push(Reverse((0, (0, 0), direction, 0)));
+    }
+    while let Some(Reverse((loss, pos, direction, run))) = queue.pop() {
+        if loss > best[index(pos, direction, run)] {
+            continue;
+        }
+        if pos == end && run >= min_run {
+            return Some(loss);
+        }
+        let straight = (direction, run + 1);
+        let turns = [(direction + 1) % 4, (direction + 3) % 4].map(|turn| (turn, 1));
+        let moves = (run < max_run)
+            .then_some(straight)
+            .into_iter()
+            .chain(turns.into_iter().filter(|_| run >= min_run));
+        for (direction, run) in moves {
+            let Some(next) = city.offset(pos, ORTHOGONAL[direction]) else {
+                continue;
+            };
+            let loss = loss + u64::from(city[next]);
+            let known = &mut best[index(next, direction, run)];
+            if loss < *known {
+                *known = loss;
+                queue.push(Reverse((loss, next, direction, run)));
+            }
+        }
+    }
+    None
+}
+
+/// Reference for [`min_heat_loss`] over whole straight runs: each move goes
+/// a number of blocks in `runs` along one axis, the next one along the other.
+pub fn min_heat_loss_segments(city: &Grid<u8>, runs: RangeInclusive<usize>) -> Option<u64> {
+    let end = (city.rows() - 1, city.cols() - 1);
+    let mut best: HashMap<(Pos, bool), u64> = HashMap::new();
+    let mut queue = BinaryHeap::new();
+    for vertical in [false, true] {
+        best.insert(((0, 0), vertical), 0);
+        queue.push(Reverse((0, (0, 0), vertical)));
+    }
+    while let Some(Reverse((loss, pos, vertical))) = queue.pop() {
+        if loss > best[&(pos, vertical)] {
+            continue;
+        }
+        if pos == end {
+            return Some(loss);
+        }
+        for direction in if vertical { [UP, DOWN] } else { [LEFT, RIGHT] } {
+            let (mut next, mut loss) = (pos, loss);
+            for run in 1..=*runs.end() {
+                let Some(block) = city.offset(next, direction) else {
+                    break;
+                };
+                next = block;
+                loss += u64::from(city[next]);
+                if run >= *runs.start()
+                    && best
+                        .get(&(next, !vertical))
+                        .is_none_or(|&known| loss < known)
+                {
+                    best.insert((next, !vertical), loss);
+                    queue.push(Reverse((loss, next, !vertical)));
+                }
+            }
+        }
+    }
+    None
+}
+
+fn solve(loss: Option<u64>) -> Result<u64, Uncrossable> {
+    loss.ok_or(Uncrossable)
+}
+
+pub fn part1(city: &Grid<u8>) -> Result<u64, Uncrossable> {
+    solve(min_heat_loss(city, CRUCIBLE))
+}
+
+pub fn part2(city: &Grid<u8>) -> Result<u64, Uncrossable> {
+    solve(min_heat_loss(city, ULTRA_CRUCIBLE))
+}
+
+/// A square city of `size` blocks a side, losing 1 to 9 heat each.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let size = size.max(MIN_SIDE);
+    let cells = (0..size * size).map(|_| rng.range(1..=9) as u8).collect();
+    Grid::new(size, size, cells).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use aoc_core::{check, generate::Rng, Day};
+
+    use super::*;
+
+    #[test]
+    fn test_rules() {
+        let input = fs::read_to_string("./input.dev.example2.txt").expect("Cannot read file");
+        let city = parse_input(&input).unwrap();
+        assert_eq!(min_heat_loss(&city, ULTRA_CRUCIBLE), Some(71));
+        // Without a limit the path is the shortest one.
+        assert_eq!(min_heat_loss(&city, 1..=100), Some(15));
+        assert_eq!(min_heat_loss(&city, 12..=12), None);
+        for runs in [CRUCIBLE, ULTRA_CRUCIBLE, 2..=5] {
+            assert_eq!(
+                min_heat_loss_segments(&city, runs.clone()),
+                min_heat_loss(&city, runs)
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("12345\n12345\n12345\n12345\n12305\n").unwrap_err();
+        assert_eq!((err.line, err.column), (5, 4));
+    }
+
+    #[test]
+    fn test_small_cities() {
+        let day = Day::of::<Day17>();
+        for (input, part1_loss) in [("7\n", 0), ("123\n", 5), ("123\n456\n789\n", 20)] {
+            let city = parse_input(input).unwrap();
+            assert_eq!(part1(&city), Ok(part1_loss), "{:?}", input);
+            check::agree(&day, input).unwrap();
+        }
+        let city = parse_input("123\n456\n789\n").unwrap();
+        assert_eq!(part2(&city), Err(Uncrossable));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 30);
+        assert_eq!(input, generate(&mut Rng::new(1), 30));
+        check::agree(&Day::of::<Day17>(), &input).unwrap();
+    }
+}
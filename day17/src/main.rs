@@ -0,0 +1,3 @@
+fn main() {
+    aoc_core::run::<day17::Day17>("./input.txt");
+}
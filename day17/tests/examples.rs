@@ -0,0 +1,26 @@
+//! Generated by `aoc examples --day 17` from the puzzle description.
+
+use aoc_core::{example::assert_examples, Day, Example, Part};
+
+const EXAMPLES: &[Example] = &[
+    Example {
+        part: Part::One,
+        file: "input.dev.example1.txt",
+        answer: "102",
+    },
+    Example {
+        part: Part::Two,
+        file: "input.dev.example1.txt",
+        answer: "94",
+    },
+    Example {
+        part: Part::Two,
+        file: "input.dev.example2.txt",
+        answer: "71",
+    },
+];
+
+#[test]
+fn test_examples() {
+    assert_examples(&Day::of::<day17::Day17>(), EXAMPLES);
+}
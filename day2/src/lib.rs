@@ -0,0 +1,255 @@
+use std::cmp::max;
+
+use aoc_core::{
+    generate::Rng,
+    num::{Answer, Int, Overflow, Total},
+    parse::{parse_lines, unsigned, IResult},
+    Alternative, ParseError, Part, Solution, Streaming,
+};
+
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    character::complete::multispace0,
+    combinator::{cut, eof},
+    error::context,
+    multi::separated_list1,
+};
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    type Input = Vec<(u32, Draw)>;
+    type Answer1 = Result<u64, Overflow>;
+    type Answer2 = Result<u64, Overflow>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64, Overflow> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64, Overflow> {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![
+            Alternative {
+                part: Part::One,
+                name: "part1_wide",
+                solve: |input| part1::<u128>(input).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_wide",
+                solve: |input| part2::<u128>(input).answer(),
+            },
+        ]
+    }
+}
+
+impl Streaming for Day2 {
+    type State = (Total<u64>, Total<u64>);
+
+    fn feed(state: &mut Self::State, record: &str) -> Result<(), ParseError> {
+        let parsed = parse_input(record)?;
+        state.0.add(part1(&parsed));
+        state.1.add(part2(&parsed));
+        Ok(())
+    }
+
+    fn finish(state: Self::State) -> Result<(Self::Answer1, Self::Answer2), ParseError> {
+        Ok((state.0.get(), state.1.get()))
+    }
+}
+
+#[derive(Debug)]
+pub struct Draw {
+    blue: u32,
+    red: u32,
+    green: u32,
+}
+
+impl Draw {
+    fn new() -> Self {
+        Draw {
+            blue: 0,
+            red: 0,
+            green: 0,
+        }
+    }
+}
+
+fn parse_color(input: &str) -> IResult<'_, &str> {
+    context(
+        "`blue`, `red` or `green`",
+        alt((tag("blue"), tag("red"), tag("green"))),
+    )(input)
+}
+fn eat_whitespace(input: &str) -> IResult<'_, &str> {
+    multispace0(input)
+}
+
+fn parse_num_color(input: &str) -> IResult<'_, (u32, &str)> {
+    let (input, num) = unsigned(input)?;
+    let (input, _) = eat_whitespace(input)?;
+    let (input, color) = parse_color(input)?;
+    Ok((input, (num, color)))
+}
+
+fn parse_draw(input: &str) -> IResult<'_, Draw> {
+    let (input, num_color_vec) = separated_list1(tag(", "), cut(parse_num_color))(input)?;
+    let mut draw = Draw::new();
+    for (num, color) in num_color_vec.iter() {
+        match *color {
+            "blue" => {
+                draw.blue = *num;
+            }
+            "red" => {
+                draw.red = *num;
+            }
+            "green" => {
+                draw.green = *num;
+            }
+            _ => {
+                unreachable!("Should not hit here");
+            }
+        }
+    }
+    Ok((input, draw))
+}
+
+fn parse_all_draws(input: &str) -> IResult<'_, Draw> {
+    let (input, all_draws) = separated_list1(tag("; "), parse_draw)(input)?;
+    let mut whole_bag = Draw::new();
+    for current_draw in all_draws.iter() {
+        whole_bag.red = max(whole_bag.red, current_draw.red);
+        whole_bag.blue = max(whole_bag.blue, current_draw.blue);
+        whole_bag.green = max(whole_bag.green, current_draw.green);
+    }
+
+    Ok((input, whole_bag))
+}
+
+fn parse_game(line: &str) -> IResult<'_, (u32, Draw)> {
+    let (line, _) = context("`Game `", tag("Game "))(line)?;
+    let (line, id) = unsigned(line)?;
+    let (line, _) = context("`: `", tag(": "))(line)?;
+    let (line, bag) = parse_all_draws(line)?;
+    let (line, _) = context("`, `, `; ` or end of line", eof)(line)?;
+    Ok((line, (id, bag)))
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<(u32, Draw)>, ParseError> {
+    parse_lines(Day2::DAY, input, parse_game)
+}
+
+pub fn part1<N: Int>(bags: &[(u32, Draw)]) -> Result<N, Overflow> {
+    N::try_sum(
+        bags.iter()
+            .filter_map(|(id, bag)| {
+                if (bag.red <= 12 && bag.green <= 13) && bag.blue <= 14 {
+                    return Some(*id);
+                }
+                None
+            })
+            .map(|id| N::of(u64::from(id))),
+    )
+}
+
+pub fn part2<N: Int>(bags: &[(u32, Draw)]) -> Result<N, Overflow> {
+    N::try_sum(bags.iter().map(|(_, bag)| {
+        let [red, blue, green] = [bag.red, bag.blue, bag.green].map(|n| N::of(u64::from(n)));
+        red?.try_mul(blue?)?.try_mul(green?)
+    }))
+}
+
+/// `size` games of one to six draws, each of one to three colors.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for id in 1..=size {
+        let draws = (0..rng.range(1..=6))
+            .map(|_| {
+                let mut colors = ["red", "green", "blue"];
+                rng.shuffle(&mut colors);
+                colors[..rng.range(1..=3) as usize]
+                    .iter()
+                    .map(|color| format!("{} {}", rng.range(1..=20), color))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            })
+            .collect::<Vec<String>>();
+        input.push_str(&format!("Game {}: {}\n", id, draws.join("; ")));
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use aoc_core::stream;
+
+    use super::*;
+
+    #[test]
+    fn test_dev_part1() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Unable to read file");
+        assert_eq!(part1(&parse_input(&input).unwrap()), Ok(8));
+    }
+
+    #[test]
+    fn test_dev_part2() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Unable to read file");
+        assert_eq!(part2(&parse_input(&input).unwrap()), Ok(2286));
+    }
+
+    #[test]
+    fn test_widths() {
+        let parsed = parse_input("Game 1: 5000 red, 5000 blue, 5000 green\n").unwrap();
+        assert_eq!(part2::<u32>(&parsed), Err(Overflow));
+        assert_eq!(part2::<u64>(&parsed), Ok(125_000_000_000));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("Game 1: 3 blue, 4 red\nGame 2: 1 red; 2 grey\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 18));
+        assert_eq!(err.expected, "`blue`, `red` or `green`");
+        let err = parse_input("Game 1 3 blue\n").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 7, "`: `")
+        );
+    }
+
+    #[test]
+    fn test_stream() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Unable to read file");
+        let parsed = parse_input(&input).unwrap();
+        assert_eq!(
+            stream::solve::<Day2>(input.as_bytes()).unwrap(),
+            (part1(&parsed), part2(&parsed))
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 100);
+        assert_eq!(input, generate(&mut Rng::new(1), 100));
+        let parsed = parse_input(&input).unwrap();
+        assert_eq!(parsed.len(), 100);
+        assert_eq!(
+            stream::solve::<Day2>(input.as_bytes()).unwrap(),
+            (part1(&parsed), part2(&parsed))
+        );
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+
+use aoc_core::{
+    generate::Rng,
+    grid::Pos,
+    num::{Answer, Int, Overflow},
+    Alternative, Grid, ParseError, Part, Solution,
+};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Input = Grid<char>;
+    type Answer1 = Result<u64, Overflow>;
+    type Answer2 = Result<u64, Overflow>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64, Overflow> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64, Overflow> {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![
+            Alternative {
+                part: Part::One,
+                name: "part1_wide",
+                solve: |input| part1::<u128>(input).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_wide",
+                solve: |input| part2::<u128>(input).answer(),
+            },
+        ]
+    }
+}
+
+pub fn parse_input(input: &str) -> Result<Grid<char>, ParseError> {
+    Grid::parse(Day3::DAY, input, "a character", Some)
+}
+
+/// `num` with `digit` appended, in `N`.
+fn append_digit<N: Int>(num: N, digit: char) -> Result<N, Overflow> {
+    num.try_mul(N::of(10u64)?)?
+        .try_add(N::of(u64::from(digit.to_digit(10).unwrap()))?)
+}
+
+pub fn part1<N: Int>(grid: &Grid<char>) -> Result<N, Overflow> {
+    let mut special_numbers: Vec<N> = vec![];
+    let mut num_accum = N::ZERO;
+    let mut is_special = false;
+    for (pos, &current_elem) in grid.iter() {
+        if current_elem.is_ascii_digit() {
+            if grid
+                .neighbours8(pos)
+                .any(|neighbour| grid[neighbour] != '.' && !grid[neighbour].is_ascii_digit())
+            {
+                is_special = true;
+            }
+            num_accum = append_digit(num_accum, current_elem)?;
+        } else {
+            if is_special {
+                special_numbers.push(num_accum);
+            }
+            num_accum = N::ZERO;
+            is_special = false;
+        }
+    }
+    N::try_sum(special_numbers.into_iter().map(Ok))
+}
+
+pub fn part2<N: Int>(grid: &Grid<char>) -> Result<N, Overflow> {
+    let mut gear_star: BTreeMap<Pos, N> = BTreeMap::new();
+    let mut accum = N::ZERO;
+    let mut num_accum = N::ZERO;
+    let mut current_star_index: Option<Pos> = None;
+    for (pos, &current_elem) in grid.iter() {
+        if current_elem.is_ascii_digit() {
+            if let Some(star) = grid
+                .neighbours8(pos)
+                .filter(|&neighbour| grid[neighbour] == '*')
+                .last()
+            {
+                current_star_index = Some(star);
+            }
+            num_accum = append_digit(num_accum, current_elem)?;
+        } else {
+            if let Some(star_index) = current_star_index {
+                if let Some(&other_num) = gear_star.get(&star_index) {
+                    accum = accum.try_add(num_accum.try_mul(other_num)?)?;
+                }
+                gear_star.insert(star_index, num_accum);
+            }
+            current_star_index = None;
+            num_accum = N::ZERO;
+        }
+    }
+    Ok(accum)
+}
+
+/// `size` rows of 140 columns with numbers of up to three digits and
+/// symbols, a third of them gears. Rows end with a `.` so that numbers never
+/// run into the next row.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const WIDTH: usize = 140;
+    const SYMBOLS: [char; 9] = ['*', '*', '*', '#', '+', '$', '/', '@', '='];
+    let mut input = String::new();
+    for _ in 0..size {
+        let mut row = String::new();
+        while row.len() < WIDTH - 4 {
+            if !row.ends_with(|c: char| c.is_ascii_digit()) && rng.one_in(6) {
+                row.push(char::from(b'0' + rng.range(1..=9) as u8));
+                for _ in 1..rng.range(1..=3) {
+                    row.push(char::from(b'0' + rng.below(10) as u8));
+                }
+            } else if rng.one_in(12) {
+                row.push(*rng.pick(&SYMBOLS));
+            } else {
+                row.push('.');
+            }
+        }
+        input.push_str(&format!("{:.<width$}\n", row, width = WIDTH));
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_dev_part1() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Cannot read file");
+        assert_eq!(part1::<u64>(&parse_input(&input).unwrap()), Ok(4361));
+    }
+
+    #[test]
+    fn test_dev_part2() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Cannot read file");
+        assert_eq!(part2::<u64>(&parse_input(&input).unwrap()), Ok(467835));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let grid = parse_input("12345678901*.\n...........1.\n").unwrap();
+        assert_eq!(part1::<u32>(&grid), Err(Overflow));
+        assert_eq!(part1::<u64>(&grid), Ok(12345678902));
+        assert_eq!(part2::<u64>(&grid), Ok(12345678901));
+        let grid = parse_input("9999999999*9999999999.\n").unwrap();
+        assert_eq!(part2::<u64>(&grid), Err(Overflow));
+        assert_eq!(part2::<u128>(&grid), Ok(99999999980000000001));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 100);
+        assert_eq!(input, generate(&mut Rng::new(1), 100));
+        let grid = parse_input(&input).unwrap();
+        assert_eq!((grid.rows(), grid.cols()), (100, 140));
+        assert!(part1::<u64>(&grid).unwrap() > 0 && part2::<u64>(&grid).unwrap() > 0);
+    }
+}
@@ -0,0 +1,261 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    slice,
+};
+
+use aoc_core::{
+    generate::Rng,
+    num::{Answer, Int, Overflow, Total},
+    parse::{numbers, parse_lines, unsigned, IResult},
+    Alternative, ParseError, Part, Solution, Streaming,
+};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{multispace0, multispace1},
+    combinator::eof,
+    error::context,
+};
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Input = Vec<Card>;
+    type Answer1 = Result<u64, Overflow>;
+    type Answer2 = Result<u64, Overflow>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64, Overflow> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64, Overflow> {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![
+            Alternative {
+                part: Part::One,
+                name: "part1_wide",
+                solve: |input| part1::<u128>(input).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_wide",
+                solve: |input| part2::<u128>(input).answer(),
+            },
+        ]
+    }
+}
+
+impl Streaming for Day4 {
+    type State = Pile;
+
+    fn feed(pile: &mut Pile, record: &str) -> Result<(), ParseError> {
+        for card in parse_input(record)? {
+            let (winning_cards, my_cards) = &card;
+            let matches = my_cards
+                .iter()
+                .filter(|card| winning_cards.contains(card))
+                .count();
+            let copies = pile
+                .copies
+                .pop_front()
+                .unwrap_or(Ok(0))
+                .and_then(|won| won.try_add(1));
+            pile.points.add(part1(slice::from_ref(&card)));
+            pile.cards.add(copies);
+            if pile.copies.len() < matches {
+                pile.copies.resize(matches, Ok(0));
+            }
+            pile.copies
+                .iter_mut()
+                .take(matches)
+                .for_each(|won| *won = won.and_then(|won| won.try_add(copies?)));
+        }
+        Ok(())
+    }
+
+    fn finish(pile: Pile) -> Result<(Self::Answer1, Self::Answer2), ParseError> {
+        Ok((pile.points.get(), pile.cards.get()))
+    }
+}
+
+/// The cards read so far: their points, how many there are with the copies,
+/// and the copies won of each of the next cards.
+#[derive(Debug, Default)]
+pub struct Pile {
+    points: Total<u64>,
+    cards: Total<u64>,
+    copies: VecDeque<Result<u64, Overflow>>,
+}
+
+/// Winning numbers and the numbers we have.
+pub type Card = (Vec<u32>, Vec<u32>);
+
+fn parse_card(input: &str) -> IResult<'_, Card> {
+    let (input, _) = context("`Card `", tag("Card "))(input)?;
+    let (input, _) = multispace0(input)?;
+    let (input, _) = unsigned::<u32>(input)?;
+    let (input, _) = context("`:`", tag(":"))(input)?;
+    let (input, _) = context("a space", multispace1)(input)?;
+    let (input, winning_cards) = numbers(input)?;
+    let (input, _) = context("a space", multispace1)(input)?;
+    let (input, _) = context("a number or `|`", tag("|"))(input)?;
+    let (input, _) = context("a space", multispace1)(input)?;
+    let (input, my_cards) = numbers(input)?;
+    let (input, _) = context("a number or end of line", eof)(input)?;
+    Ok((input, (winning_cards, my_cards)))
+}
+
+pub fn parse_input(input: &str) -> Result<Vec<Card>, ParseError> {
+    parse_lines(Day4::DAY, input, parse_card)
+}
+
+pub fn part1<N: Int>(cards: &[Card]) -> Result<N, Overflow> {
+    let two = N::of(2u64)?;
+    N::try_sum(cards.iter().map(|(winning_cards, my_cards)| {
+        let nums = my_cards
+            .iter()
+            .filter(|card| winning_cards.contains(card))
+            .count();
+        match nums {
+            0 => Ok(N::ZERO),
+            _ => (1..nums).try_fold(N::ONE, |points, _| points.try_mul(two)),
+        }
+    }))
+}
+
+pub fn part2<N: Int>(cards: &[Card]) -> Result<N, Overflow> {
+    let mut lookup: BTreeMap<u32, N> = BTreeMap::new();
+    let tot_cards = cards.len();
+    for i in 1..=tot_cards {
+        lookup.insert(i as u32, N::ONE);
+    }
+    cards
+        .iter()
+        .enumerate()
+        .try_for_each(|(indx, (winning_cards, my_cards))| {
+            let indx = indx as u32;
+            let nums = my_cards
+                .iter()
+                .filter(|card| winning_cards.contains(card))
+                .count() as u32;
+            let current_card_num = *lookup.get(&(indx + 1)).unwrap();
+            for num in indx + 2..indx + nums + 2 {
+                if let Some(value) = lookup.get_mut(&num) {
+                    *value = value.try_add(current_card_num)?;
+                }
+            }
+            Ok(())
+        })?;
+    N::try_sum(lookup.values().copied().map(Ok))
+}
+
+/// `size` cards of 10 winning numbers and 25 numbers we have. Few cards
+/// win, so that the copies won stay far from overflowing, and none wins
+/// copies of cards past the end.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let width = size.to_string().len();
+    let mut input = String::new();
+    for indx in 0..size {
+        let matches = if rng.one_in(50) {
+            rng.range(4..=10)
+        } else if rng.one_in(3) {
+            rng.range(1..=3)
+        } else {
+            0
+        };
+        let matches = matches.min((size - indx - 1) as u64) as usize;
+        let mut numbers = (1..=99).collect::<Vec<u32>>();
+        rng.shuffle(&mut numbers);
+        let mut mine = numbers[..matches].to_vec();
+        mine.extend_from_slice(&numbers[10..35 - matches]);
+        rng.shuffle(&mut mine);
+        let row = |numbers: &[u32]| {
+            numbers
+                .iter()
+                .map(|number| format!("{:>2}", number))
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+        input.push_str(&format!(
+            "Card {:>width$}: {} | {}\n",
+            indx + 1,
+            row(&numbers[..10]),
+            row(&mine),
+            width = width
+        ));
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use aoc_core::stream;
+
+    use super::*;
+
+    #[test]
+    fn test_dev_part1() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Cannot read file to string");
+        assert_eq!(part1(&parse_input(&input).unwrap()), Ok(13));
+    }
+
+    #[test]
+    fn test_dev_part2() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Cannot read file to string");
+        assert_eq!(part2(&parse_input(&input).unwrap()), Ok(30));
+    }
+
+    #[test]
+    fn test_widths() {
+        let numbers = (1..=33)
+            .map(|n| n.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let parsed = parse_input(&format!("Card 1: {0} | {0}\n", numbers)).unwrap();
+        assert_eq!(part1::<u32>(&parsed), Err(Overflow));
+        assert_eq!(part1::<u64>(&parsed), Ok(1 << 32));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("Card 1: 41 48 | 83 86\nCard 2: 13 x2 | 61 30\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 12));
+        assert_eq!(err.expected, "a number or `|`");
+    }
+
+    #[test]
+    fn test_stream() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Cannot read file to string");
+        let parsed = parse_input(&input).unwrap();
+        assert_eq!(
+            stream::solve::<Day4>(input.as_bytes()).unwrap(),
+            (part1(&parsed), part2(&parsed))
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 100);
+        assert_eq!(input, generate(&mut Rng::new(1), 100));
+        let parsed = parse_input(&input).unwrap();
+        assert_eq!(parsed.len(), 100);
+        assert_eq!(
+            stream::solve::<Day4>(input.as_bytes()).unwrap(),
+            (part1(&parsed), part2(&parsed))
+        );
+    }
+}
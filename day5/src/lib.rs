@@ -0,0 +1,540 @@
+use std::{
+    cmp::{max, min},
+    mem,
+};
+
+use aoc_core::{
+    generate::Rng,
+    num::{Answer, Int, Overflow},
+    parse::{lines, numbers, parse_all, section, unsigned, IResult},
+    stream::{Separator, Streaming},
+    trace::{self, Event},
+    Alternative, ParseError, Part, Solution,
+};
+
+use nom::{
+    character::complete::{char, multispace0},
+    combinator::{cut, map, verify},
+    error::context,
+    sequence::{preceded, tuple},
+};
+use rayon::prelude::*;
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    type Input = FieldMap;
+    type Answer1 = Result<u64, Overflow>;
+    type Answer2 = Result<u64, Overflow>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64, Overflow> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64, Overflow> {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![
+            Alternative {
+                part: Part::One,
+                name: "part1_parallel",
+                solve: |input| part1_parallel(input).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_parallel",
+                solve: |input| part2_parallel(input).answer(),
+            },
+            Alternative {
+                part: Part::One,
+                name: "part1_wide",
+                solve: |input| part1::<u128>(input).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_wide",
+                solve: |input| part2::<u128>(input).answer(),
+            },
+        ]
+    }
+}
+
+/// The seeds section comes first, then every map is applied to the seeds and
+/// seed ranges as soon as it is read.
+impl Streaming for Day5 {
+    const SEPARATOR: Separator = Separator::Block;
+    type State = Almanac;
+
+    fn feed(almanac: &mut Almanac, record: &str) -> Result<(), ParseError> {
+        match almanac.stage {
+            0 => {
+                let seeds = parse_all(Day5::DAY, record, parse_seeds)?;
+                almanac.ranges = seed_ranges(&seeds);
+                almanac.values = Ok(seeds);
+            }
+            stage @ 1..=7 => {
+                let header = HEADERS[stage - 1];
+                let stage = parse_all(Day5::DAY, record, section(header, lines(parse_range)))?;
+                let entries = entries(&stage);
+                let entries = entries.as_ref().map_err(|&err| err);
+                let values = mem::replace(&mut almanac.values, Err(Overflow));
+                almanac.values = values.and_then(|values| {
+                    values
+                        .into_iter()
+                        .map(|value| map_value(value, entries?))
+                        .collect()
+                });
+                let ranges = mem::replace(&mut almanac.ranges, Err(Overflow));
+                almanac.ranges = ranges.and_then(|ranges| map_ranges(ranges, entries?));
+            }
+            _ => return Err(ParseError::new(Day5::DAY, record, 0, "end of input")),
+        }
+        almanac.stage += 1;
+        Ok(())
+    }
+
+    fn finish(almanac: Almanac) -> Result<(Self::Answer1, Self::Answer2), ParseError> {
+        match almanac.stage {
+            0 => Err(ParseError::new(Day5::DAY, "", 0, "seeds:")),
+            stage @ 1..=7 => Err(ParseError::new(Day5::DAY, "", 0, HEADERS[stage - 1])),
+            _ => Ok((
+                almanac
+                    .values
+                    .map(|values| values.into_iter().min().unwrap()),
+                almanac
+                    .ranges
+                    .map(|ranges| ranges.iter().map(|range| range.0).min().unwrap()),
+            )),
+        }
+    }
+}
+
+/// The seeds and seed ranges after the maps read so far.
+#[derive(Debug)]
+pub struct Almanac {
+    stage: usize,
+    values: Result<Vec<u64>, Overflow>,
+    ranges: Result<Vec<(u64, u64)>, Overflow>,
+}
+
+impl Default for Almanac {
+    fn default() -> Self {
+        Almanac {
+            stage: 0,
+            values: Ok(vec![]),
+            ranges: Ok(vec![]),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FieldMap {
+    seeds: Vec<u64>,
+    /// The seven maps, in the order a seed passes through them.
+    stages: Vec<Vec<Vec<u64>>>,
+}
+
+const HEADERS: [&str; 7] = [
+    "seed-to-soil map:",
+    "soil-to-fertilizer map:",
+    "fertilizer-to-water map:",
+    "water-to-light map:",
+    "light-to-temperature map:",
+    "temperature-to-humidity map:",
+    "humidity-to-location map:",
+];
+
+/// `destination source length`, a line that starts with a number must hold
+/// all three.
+fn parse_range(input: &str) -> IResult<'_, Vec<u64>> {
+    map(
+        tuple((
+            unsigned,
+            cut(preceded(char(' '), unsigned)),
+            cut(preceded(char(' '), unsigned)),
+        )),
+        |(dest, source, stride)| vec![dest, source, stride],
+    )(input)
+}
+
+/// The seeds, which part 2 reads in pairs.
+fn parse_seeds(input: &str) -> IResult<'_, Vec<u64>> {
+    section(
+        "seeds:",
+        context(
+            "an even number of seeds",
+            verify(numbers, |seeds: &Vec<u64>| seeds.len().is_multiple_of(2)),
+        ),
+    )(input)
+}
+
+fn parse_almanac(input: &str) -> IResult<'_, FieldMap> {
+    let (mut input, seeds) = parse_seeds(input)?;
+    let mut stages = vec![];
+    for header in HEADERS {
+        let (rest, ranges) = preceded(multispace0, section(header, lines(parse_range)))(input)?;
+        stages.push(ranges);
+        input = rest;
+    }
+    Ok((input, FieldMap { seeds, stages }))
+}
+
+pub fn parse_input(input: &str) -> Result<FieldMap, ParseError> {
+    parse_all(Day5::DAY, input, parse_almanac)
+}
+
+/// A map entry as `(destination, source start, source end)`.
+type Entry<N> = (N, N, N);
+
+/// The entries of one map in `N`.
+fn entries<N: Int>(stage: &[Vec<u64>]) -> Result<Vec<Entry<N>>, Overflow> {
+    stage
+        .iter()
+        .map(|range| {
+            let source_start = N::of(range[1])?;
+            Ok((
+                N::of(range[0])?,
+                source_start,
+                source_start.try_add(N::of(range[2])?)?,
+            ))
+        })
+        .collect()
+}
+
+/// The entries of every map in `N`.
+fn stages<N: Int>(field_map: &FieldMap) -> Result<Vec<Vec<Entry<N>>>, Overflow> {
+    field_map
+        .stages
+        .iter()
+        .map(|stage| entries(stage))
+        .collect()
+}
+
+/// Looks `value` up in one map, values outside every range map to themselves.
+fn map_value<N: Int>(value: N, stage: &[Entry<N>]) -> Result<N, Overflow> {
+    match stage
+        .iter()
+        .find(|&&(_, source_start, source_end)| (source_start..source_end).contains(&value))
+    {
+        Some(&(dest_start, source_start, _)) => dest_start.try_add(value.try_sub(source_start)?),
+        None => Ok(value),
+    }
+}
+
+/// Where `seed` ends up after every map.
+fn location<N: Int>(seed: u64, stages: &[Vec<Entry<N>>]) -> Result<N, Overflow> {
+    stages
+        .iter()
+        .try_fold(N::of(seed)?, |value, stage| map_value(value, stage))
+}
+
+pub fn part1<N: Int>(field_map: &FieldMap) -> Result<N, Overflow> {
+    let stages = stages(field_map)?;
+    let locations = field_map
+        .seeds
+        .iter()
+        .map(|&seed| location(seed, &stages))
+        .collect::<Result<Vec<N>, Overflow>>()?;
+    Ok(locations.into_iter().min().unwrap())
+}
+
+/// A range that only partially overlaps a map entry, the parts outside the
+/// entry are mapped on their own.
+#[derive(Debug)]
+pub struct Split<N> {
+    pub range: (N, N),
+    pub overlap: (N, N),
+    pub below: Option<(N, N)>,
+    pub above: Option<(N, N)>,
+}
+
+impl<N: Int> Event for Split<N> {
+    fn name(&self) -> &'static str {
+        "split"
+    }
+}
+
+/// Sends every range in `ranges` (start inclusive, end exclusive) through one
+/// map, splitting a range wherever it only partially overlaps a map entry.
+fn map_ranges<N: Int>(ranges: Vec<(N, N)>, stage: &[Entry<N>]) -> Result<Vec<(N, N)>, Overflow> {
+    let mut processed: Vec<(N, N)> = vec![];
+    let mut to_be_processed = ranges;
+    while let Some(working_range) = to_be_processed.pop() {
+        let overlap = stage
+            .iter()
+            .find_map(|&(dest_strt, source_strt, source_end)| {
+                let overlap_start = max(source_strt, working_range.0);
+                let overlap_end = min(source_end, working_range.1);
+                (overlap_start < overlap_end).then_some((
+                    dest_strt,
+                    source_strt,
+                    overlap_start,
+                    overlap_end,
+                ))
+            });
+        match overlap {
+            Some((dest_strt, source_strt, overlap_start, overlap_end)) => {
+                processed.push((
+                    dest_strt.try_add(overlap_start.try_sub(source_strt)?)?,
+                    dest_strt.try_add(overlap_end.try_sub(source_strt)?)?,
+                ));
+                let below =
+                    (working_range.0 < overlap_start).then_some((working_range.0, overlap_start));
+                let above =
+                    (overlap_end < working_range.1).then_some((overlap_end, working_range.1));
+                if below.is_some() || above.is_some() {
+                    trace::emit(|| Split {
+                        range: working_range,
+                        overlap: (overlap_start, overlap_end),
+                        below,
+                        above,
+                    });
+                }
+                to_be_processed.extend(below);
+                to_be_processed.extend(above);
+            }
+            None => processed.push(working_range),
+        }
+    }
+    Ok(processed)
+}
+
+/// The seed numbers read as pairs of range start and length.
+fn seed_ranges<N: Int>(seeds: &[u64]) -> Result<Vec<(N, N)>, Overflow> {
+    seeds
+        .chunks(2)
+        .map(|seed_range_chunk| {
+            let seed_range_start = N::of(seed_range_chunk[0])?;
+            let seed_range_stride = N::of(seed_range_chunk[1])?;
+            Ok((
+                seed_range_start,
+                seed_range_start.try_add(seed_range_stride)?,
+            ))
+        })
+        .collect()
+}
+
+pub fn part2<N: Int>(field_map: &FieldMap) -> Result<N, Overflow> {
+    let ranges = stages(field_map)?
+        .iter()
+        .try_fold(seed_ranges(&field_map.seeds)?, |ranges, stage| {
+            map_ranges(ranges, stage)
+        })?;
+    Ok(ranges.iter().map(|range| range.0).min().unwrap())
+}
+
+/// Same as [`part1`], one seed per task.
+pub fn part1_parallel(field_map: &FieldMap) -> Result<u64, Overflow> {
+    let stages = stages(field_map)?;
+    let locations = field_map
+        .seeds
+        .par_iter()
+        .map(|&seed| location(seed, &stages))
+        .collect::<Result<Vec<u64>, Overflow>>()?;
+    Ok(locations.into_iter().min().unwrap())
+}
+
+/// Same as [`part2`], one seed range per task. A range is split without
+/// looking at the others, so the ranges can go through the maps on their own.
+pub fn part2_parallel(field_map: &FieldMap) -> Result<u64, Overflow> {
+    let stages = stages(field_map)?;
+    let lowest = seed_ranges(&field_map.seeds)?
+        .into_par_iter()
+        .map(|range| {
+            let ranges = stages
+                .iter()
+                .try_fold(vec![range], |ranges, stage| map_ranges(ranges, stage))?;
+            Ok(ranges.iter().map(|range| range.0).min().unwrap())
+        })
+        .collect::<Result<Vec<u64>, Overflow>>()?;
+    Ok(lowest.into_iter().min().unwrap())
+}
+
+/// An almanac of 10 seed ranges and seven maps of about `size` ranges each.
+/// Every map cuts the 32 bit numbers into pieces and shuffles them, leaving
+/// out a few pieces, but never all, that then map to themselves.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const LIMIT: u64 = 1 << 32;
+    let seeds = (0..10)
+        .map(|_| {
+            let start = rng.below(LIMIT);
+            format!(
+                "{} {}",
+                start,
+                rng.range(1..=(LIMIT - start).min(LIMIT / 20))
+            )
+        })
+        .collect::<Vec<String>>();
+    let mut input = format!("seeds: {}\n", seeds.join(" "));
+    for header in HEADERS {
+        let mut cuts = (1..size.max(1))
+            .map(|_| rng.range(1..=LIMIT - 1))
+            .collect::<Vec<u64>>();
+        cuts.extend([0, LIMIT]);
+        cuts.sort_unstable();
+        cuts.dedup();
+        let pieces = cuts
+            .windows(2)
+            .map(|cut| (cut[0], cut[1] - cut[0]))
+            .collect::<Vec<(u64, u64)>>();
+        let mut order = (0..pieces.len()).collect::<Vec<usize>>();
+        rng.shuffle(&mut order);
+        let mut destinations = vec![0; pieces.len()];
+        let mut start = 0;
+        for indx in order {
+            destinations[indx] = start;
+            start += pieces[indx].1;
+        }
+        let mut lines = pieces
+            .iter()
+            .zip(destinations)
+            .map(|((source, length), destination)| {
+                format!("{} {} {}\n", destination, source, length)
+            })
+            .collect::<Vec<String>>();
+        rng.shuffle(&mut lines);
+        input.push_str(&format!("\n{}\n", header));
+        for (indx, line) in lines.into_iter().enumerate() {
+            if indx == 0 || !rng.one_in(10) {
+                input.push_str(&line);
+            }
+        }
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use aoc_core::stream;
+
+    use super::*;
+
+    #[test]
+    fn test_dev_part1() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Cannot read file");
+        assert_eq!(part1(&parse_input(&input).unwrap()), Ok(35));
+    }
+
+    #[test]
+    fn test_dev_part2() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Cannot read file");
+        assert_eq!(part2(&parse_input(&input).unwrap()), Ok(46));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Cannot read file");
+        let err = parse_input(&input.replace("52 50 48", "52 50")).unwrap_err();
+        assert_eq!((err.line, err.column), (5, 6));
+        assert_eq!(err.expected, "` `");
+        let err = parse_input(&input.replace("seeds: 79 14", "seeds: 79")).unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 8, "an even number of seeds")
+        );
+        let err = parse_input(&input.replace("water-to-light", "water-to-lamp")).unwrap_err();
+        assert_eq!(
+            (err.line, err.expected.as_str()),
+            (18, "water-to-light map:")
+        );
+    }
+
+    #[test]
+    fn test_stream() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Cannot read file");
+        let parsed = parse_input(&input).unwrap();
+        assert_eq!(
+            stream::solve::<Day5>(input.as_bytes()).unwrap(),
+            (part1(&parsed), part2(&parsed))
+        );
+        let err = stream::solve::<Day5>(input.replace("52 50 48", "52 50").as_bytes());
+        let Err(stream::StreamError::Parse(err)) = err else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((err.line, err.column), (5, 6));
+        let err = stream::solve::<Day5>(input.replace("seeds: 79 14", "seeds: 79").as_bytes());
+        let Err(stream::StreamError::Parse(err)) = err else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(err.expected, "an even number of seeds");
+        let truncated = &input[..input.find("\n\nhumidity").unwrap()];
+        let Err(stream::StreamError::Parse(err)) = stream::solve::<Day5>(truncated.as_bytes())
+        else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(err.expected, "humidity-to-location map:");
+    }
+
+    #[test]
+    fn test_trace() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Cannot read file");
+        let field_map = parse_input(&input).unwrap();
+        let (lowest, print) = trace::with(trace::Print(Vec::new()), || part2::<u64>(&field_map));
+        assert_eq!(lowest, Ok(46));
+        let print = String::from_utf8(print.0).unwrap();
+        assert_eq!(
+            print.lines().next(),
+            Some(
+                "Split { range: (57, 70), overlap: (57, 61), below: None, above: Some((61, 70)) }"
+            )
+        );
+    }
+
+    #[test]
+    fn test_widths() {
+        // The seed-to-soil map sends the seeds past the end of 64 bits.
+        let maps = HEADERS
+            .iter()
+            .enumerate()
+            .map(|(indx, header)| match indx {
+                0 => format!("{}\n{} 0 100\n", header, u64::MAX),
+                _ => format!("{}\n0 1000 1\n", header),
+            })
+            .collect::<Vec<String>>();
+        let input = format!("seeds: 10 5\n\n{}", maps.join("\n"));
+        let field_map = parse_input(&input).unwrap();
+        assert_eq!(part1::<u64>(&field_map), Err(Overflow));
+        assert_eq!(part1::<u128>(&field_map), Ok(u128::from(u64::MAX) + 5));
+        assert_eq!(part2::<u64>(&field_map), Err(Overflow));
+        assert_eq!(part2::<u128>(&field_map), Ok(u128::from(u64::MAX) + 10));
+        assert_eq!(
+            stream::solve::<Day5>(input.as_bytes()).unwrap(),
+            (Err(Overflow), Err(Overflow))
+        );
+    }
+
+    #[test]
+    fn test_parallel() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Cannot read file");
+        let field_map = parse_input(&input).unwrap();
+        assert_eq!(part1_parallel(&field_map), part1(&field_map));
+        assert_eq!(part2_parallel(&field_map), part2(&field_map));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 40);
+        assert_eq!(input, generate(&mut Rng::new(1), 40));
+        let field_map = parse_input(&input).unwrap();
+        assert_eq!(field_map.seeds.len(), 20);
+        assert!(field_map.stages.iter().all(|stage| stage.len() > 30));
+        assert_eq!(
+            stream::solve::<Day5>(input.as_bytes()).unwrap(),
+            (part1(&field_map), part2(&field_map))
+        );
+        assert_eq!(part2_parallel(&field_map), part2(&field_map));
+    }
+}
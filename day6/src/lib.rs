@@ -0,0 +1,270 @@
+use aoc_core::{
+    generate::Rng,
+    num::{Answer, Int, Overflow},
+    parse::{parse_all, section, IResult},
+    Alternative, ParseError, Part, Solution,
+};
+
+use nom::{
+    character::complete::{digit1, line_ending, space1},
+    error::context,
+    multi::separated_list1,
+    sequence::preceded,
+};
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    type Input = Sheet;
+    type Answer1 = Result<u64, Overflow>;
+    type Answer2 = Result<u64, Overflow>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64, Overflow> {
+        part1(&input.races)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64, Overflow> {
+        part2(&input.kerned)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![
+            Alternative {
+                part: Part::One,
+                name: "part1_wide",
+                solve: |input| part1::<u128>(&input.races).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_wide",
+                solve: |input| part2::<u128>(&input.kerned).answer(),
+            },
+        ]
+    }
+}
+
+/// The sheet read both ways: as separate races, and with the spaces between
+/// the numbers ignored. The numbers are read as wide as they can be solved
+/// in, the parts check that they fit the type they compute in.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Sheet {
+    pub races: Vec<Vec<u64>>,
+    pub kerned: Vec<u128>,
+}
+
+/// The numbers after `label` on `line`, as slices of `input`.
+fn parse_row<'a>(input: &str, line: &'a str, label: &str) -> Result<Vec<&'a str>, ParseError> {
+    let Some(numbers) = line.strip_prefix(label) else {
+        return Err(ParseError::at(
+            Day6::DAY,
+            input,
+            line,
+            format!("`{}`", label),
+        ));
+    };
+    let row = numbers
+        .split(' ')
+        .filter(|val| !val.is_empty())
+        .map(|val| match val.find(|c: char| !c.is_ascii_digit()) {
+            Some(indx) => Err(ParseError::at(Day6::DAY, input, &val[indx..], "a number")),
+            None => Ok(val),
+        })
+        .collect::<Result<Vec<&str>, ParseError>>()?;
+    if row.is_empty() {
+        return Err(ParseError::at(
+            Day6::DAY,
+            input,
+            &line[line.len()..],
+            "a number",
+        ));
+    }
+    Ok(row)
+}
+
+pub fn parse_input(input: &str) -> Result<Sheet, ParseError> {
+    let mut lines = input.lines();
+    let mut rows = vec![];
+    for label in ["Time:", "Distance:"] {
+        let Some(line) = lines.next() else {
+            let expected = format!("`{}`", label);
+            return Err(ParseError::new(Day6::DAY, input, input.len(), expected));
+        };
+        rows.push(parse_row(input, line, label)?);
+    }
+    if let Some(line) = lines.next() {
+        return Err(ParseError::at(Day6::DAY, input, line, "end of input"));
+    }
+    to_sheet(input, &rows)
+}
+
+fn parse_sheet(input: &str) -> IResult<'_, Vec<Vec<&str>>> {
+    let row = || separated_list1(space1, context("a number", digit1));
+    let (input, times) = section("Time:", row())(input)?;
+    let (input, distances) = preceded(line_ending, section("Distance:", row()))(input)?;
+    Ok((input, vec![times, distances]))
+}
+
+/// Same as [`parse_input`], built from nom parsers.
+pub fn parse_input_nom(input: &str) -> Result<Sheet, ParseError> {
+    let rows = parse_all(Day6::DAY, input, parse_sheet)?;
+    to_sheet(input, &rows)
+}
+
+/// Reads the digits of the time and distance rows, which are slices of
+/// `input`.
+fn to_sheet(input: &str, rows: &[Vec<&str>]) -> Result<Sheet, ParseError> {
+    if rows[0].len() != rows[1].len() {
+        let line = input.lines().nth(1).unwrap();
+        let expected = format!("{} distances", rows[0].len());
+        return Err(ParseError::at(
+            Day6::DAY,
+            input,
+            &line[line.len()..],
+            expected,
+        ));
+    }
+    let races = rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|val| {
+                    val.parse::<u64>()
+                        .map_err(|_| ParseError::at(Day6::DAY, input, val, "a 64 bit number"))
+                })
+                .collect::<Result<Vec<u64>, ParseError>>()
+        })
+        .collect::<Result<Vec<Vec<u64>>, ParseError>>()?;
+    let kerned = rows
+        .iter()
+        .map(|row| {
+            row.concat().parse::<u128>().map_err(|_| {
+                ParseError::at(Day6::DAY, input, row[0], "digits forming a 128 bit number")
+            })
+        })
+        .collect::<Result<Vec<u128>, ParseError>>()?;
+    Ok(Sheet { races, kerned })
+}
+
+/// How many ways of holding the button beat `record` in a race of `time`,
+/// computed in `N`.
+fn ways<N: Int>(time: N, record: N) -> Result<N, Overflow> {
+    let mut ways = N::ZERO;
+    let mut button_time = N::ZERO;
+    loop {
+        let distance = time.try_sub(button_time)?.try_mul(button_time)?;
+        if distance > record {
+            ways = ways.try_add(N::ONE)?;
+        }
+        if button_time == time {
+            return Ok(ways);
+        }
+        button_time = button_time.try_add(N::ONE)?;
+    }
+}
+
+pub fn part1<N: Int>(parsed: &[Vec<u64>]) -> Result<N, Overflow> {
+    let times = &parsed[0];
+    let distances = &parsed[1];
+    assert_eq!(times.len(), distances.len());
+    times
+        .iter()
+        .zip(distances.iter())
+        .try_fold(N::ONE, |product, (&time, &distance)| {
+            product.try_mul(ways(N::of(time)?, N::of(distance)?)?)
+        })
+}
+
+pub fn part2<N: Int>(parsed: &[u128]) -> Result<N, Overflow> {
+    ways(N::of(parsed[0])?, N::of(parsed[1])?)
+}
+
+/// A sheet of races of one to three digit times that read as a `size` digit
+/// time when kerned, which is what part 2 iterates over. `size` is clamped
+/// to 2..=9 so that both answers fit. Every record can be beaten.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut digits = size.clamp(2, 9) as u32;
+    let mut races = vec![];
+    while digits > 0 {
+        let len = rng.range(1..=3.min(digits as u64)) as u32;
+        let time = rng.range(10u64.pow(len - 1).max(2)..=10u64.pow(len) - 1);
+        let record = rng.below(time / 2 * (time - time / 2));
+        races.push((time.to_string(), record.to_string()));
+        digits -= len;
+    }
+    let (mut times, mut distances) = (String::from("Time:    "), String::from("Distance:"));
+    for (time, record) in races {
+        let width = time.len().max(record.len()) + 3;
+        times.push_str(&format!("{:>width$}", time, width = width));
+        distances.push_str(&format!("{:>width$}", record, width = width));
+    }
+    format!("{}\n{}\n", times, distances)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_dev_part1() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Cannot read file");
+        assert_eq!(part1::<u64>(&parse_input(&input).unwrap().races), Ok(288));
+    }
+
+    #[test]
+    fn test_dev_part2() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Cannot read file");
+        assert_eq!(
+            part2::<u64>(&parse_input(&input).unwrap().kerned),
+            Ok(71503)
+        );
+    }
+
+    #[test]
+    fn test_wide() {
+        let sheet = parse_input("Time:      7  15\nDistance:  99999999999  999999999\n").unwrap();
+        assert_eq!(part1::<u64>(&sheet.races), Ok(0));
+        assert_eq!(part2::<u64>(&sheet.kerned), Err(Overflow));
+        assert_eq!(part2::<u128>(&sheet.kerned), Ok(0));
+        assert_eq!(part1::<u32>(&sheet.races), Err(Overflow));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("Time:      7  15   30\nDistance:  9  4O  200\n").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (2, 16, "a number")
+        );
+        let err = parse_input("Time:      7  15   30\r\nDistance:  9  40  200\n\n").unwrap_err();
+        assert_eq!((err.line, err.expected.as_str()), (3, "end of input"));
+    }
+
+    #[test]
+    fn test_nom_parser() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Cannot read file");
+        assert_eq!(parse_input_nom(&input), parse_input(&input));
+        let err = parse_input_nom("Time:      7  15   30\nDistance:  9  4O  200\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 16));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 6);
+        assert_eq!(input, generate(&mut Rng::new(1), 6));
+        let sheet = parse_input(&input).unwrap();
+        assert_eq!(sheet.kerned[0].to_string().len(), 6);
+        assert!(part1::<u64>(&sheet.races).unwrap() > 0);
+        assert!(u128::from(part2::<u64>(&sheet.kerned).unwrap()) <= sheet.kerned[0]);
+    }
+}
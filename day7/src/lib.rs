@@ -0,0 +1,479 @@
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BTreeSet, HashSet},
+};
+
+use aoc_core::{
+    generate::Rng,
+    num::{Answer, Int, Overflow},
+    Alternative, ParseError, Part, Solution, Streaming,
+};
+
+use rayon::prelude::*;
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    type Input = Vec<Turn>;
+    type Answer1 = Result<u64, Overflow>;
+    type Answer2 = Result<u64, Overflow>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64, Overflow> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64, Overflow> {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![
+            Alternative {
+                part: Part::One,
+                name: "part1_counts",
+                solve: |input| part1_counts(input).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_substitute",
+                solve: |input| part2_substitute(input).answer(),
+            },
+            Alternative {
+                part: Part::One,
+                name: "part1_parallel",
+                solve: |input| part1_parallel(input).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_parallel",
+                solve: |input| part2_parallel(input).answer(),
+            },
+            Alternative {
+                part: Part::One,
+                name: "part1_wide",
+                solve: |input| part1::<u128>(input).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_wide",
+                solve: |input| part2::<u128>(input).answer(),
+            },
+        ]
+    }
+}
+
+/// Ranking needs every hand, so the parsed turns are kept rather than the
+/// text they were read from, along with the hands dealt so far.
+impl Streaming for Day7 {
+    type State = (Vec<Turn>, HashSet<String>);
+
+    fn feed((turns, hands): &mut Self::State, record: &str) -> Result<(), ParseError> {
+        for turn in parse_input(record)? {
+            if !hands.insert(turn.hand.clone()) {
+                return Err(ParseError::new(Day7::DAY, record, 0, DEALT));
+            }
+            turns.push(turn);
+        }
+        Ok(())
+    }
+
+    fn finish((turns, _): Self::State) -> Result<(Self::Answer1, Self::Answer2), ParseError> {
+        Ok((part1(&turns), part2(&turns)))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Turn {
+    hand: String,
+    bid: u64,
+}
+
+#[derive(Debug, Ord, Eq, PartialEq, PartialOrd)]
+pub enum HandType {
+    FiveOfKind,
+    FourOfKind,
+    FullHouse,
+    ThreeOfKind,
+    TwoPair,
+    OnePair,
+    HighCard,
+}
+
+const CARDS: &str = "AKQJT98765432";
+
+/// Equal hands could not be ranked.
+const DEALT: &str = "a hand not dealt before";
+
+pub fn parse_input(input: &str) -> Result<Vec<Turn>, ParseError> {
+    let mut hands = HashSet::new();
+    input
+        .lines()
+        .map(|line| {
+            let err = |rest: &str, expected: &str| ParseError::at(Day7::DAY, input, rest, expected);
+            let (hand, bid_string) = line.split_at(line.find(' ').unwrap_or(line.len()));
+            if let Some(indx) = hand.find(|card| !CARDS.contains(card)) {
+                return Err(err(&hand[indx..], "a card"));
+            }
+            if hand.len() != 5 {
+                return Err(err(&hand[hand.len().min(5)..], "a hand of 5 cards"));
+            }
+            if !hands.insert(hand) {
+                return Err(err(hand, DEALT));
+            }
+            let Some(bid_string) = bid_string.strip_prefix(' ') else {
+                return Err(err(bid_string, "a space and a bid"));
+            };
+            let bid = bid_string
+                .parse::<u64>()
+                .map_err(|_| err(bid_string, "a bid"))?;
+            Ok(Turn {
+                hand: hand.to_string(),
+                bid,
+            })
+        })
+        .collect::<Result<Vec<_>, ParseError>>()
+}
+
+pub fn find_hand_type(hand: &str) -> HandType {
+    let set_from_hand = hand.chars().collect::<BTreeSet<char>>();
+    match set_from_hand.len() {
+        1 => HandType::FiveOfKind,
+        2 => {
+            let map = hand.chars().fold(BTreeMap::new(), |mut acc, card| {
+                if acc.contains_key(&card) {
+                    let value = acc.get_mut(&card).unwrap();
+                    *value += 1;
+                    return acc;
+                }
+                acc.insert(card, 1);
+                acc
+            });
+            if map.values().filter(|val| **val == 4).count() == 0 {
+                return HandType::FullHouse;
+            }
+            HandType::FourOfKind
+        }
+        3 => {
+            let map = hand.chars().fold(BTreeMap::new(), |mut acc, card| {
+                if acc.contains_key(&card) {
+                    let value = acc.get_mut(&card).unwrap();
+                    *value += 1;
+                    return acc;
+                }
+                acc.insert(card, 1);
+                acc
+            });
+            if map.values().filter(|val| **val == 3).count() == 0 {
+                return HandType::TwoPair;
+            }
+            HandType::ThreeOfKind
+        }
+        4 => HandType::OnePair,
+        5 => HandType::HighCard,
+        _ => unreachable!(),
+    }
+}
+
+pub fn part1<N: Int>(turns: &[Turn]) -> Result<N, Overflow> {
+    const POKER_ORDER: [char; 13] = [
+        'A', 'K', 'Q', 'J', 'T', '9', '8', '7', '6', '5', '4', '3', '2',
+    ];
+    let mut parsed = turns.to_vec();
+    parsed.sort_by(|a, b| {
+        let f_hand_type = find_hand_type(&a.hand);
+        let s_hand_type = find_hand_type(&b.hand);
+        match f_hand_type.cmp(&s_hand_type) {
+            std::cmp::Ordering::Equal => {
+                let (non_match_1, non_match2) = a
+                    .hand
+                    .chars()
+                    .zip(b.hand.chars())
+                    .find(|(val1, val2)| val1 != val2)
+                    .unwrap();
+                let pos1 = POKER_ORDER.iter().position(|&x| x == non_match_1).unwrap();
+                let pos2 = POKER_ORDER.iter().position(|&x| x == non_match2).unwrap();
+                pos2.cmp(&pos1)
+            }
+            _ => s_hand_type.cmp(&f_hand_type),
+        }
+    });
+
+    total(parsed.iter().map(|turn| turn.bid))
+}
+
+pub fn modified_hand_type_with_joker(hand: &str) -> HandType {
+    let j_count = hand
+        .chars()
+        .fold(0, |acc, x| if x == 'J' { acc + 1 } else { acc });
+    let hand_type = find_hand_type(hand);
+    match hand_type {
+        HandType::FiveOfKind => hand_type,
+        HandType::FourOfKind => match j_count {
+            4 | 1 => HandType::FiveOfKind,
+            0 => HandType::FourOfKind,
+            _ => unreachable!(),
+        },
+        HandType::FullHouse => match j_count {
+            3 | 2 => HandType::FiveOfKind,
+            _ => HandType::FullHouse,
+        },
+        HandType::ThreeOfKind => match j_count {
+            3 | 1 => HandType::FourOfKind,
+            0 => HandType::ThreeOfKind,
+            _ => unreachable!(),
+        },
+        HandType::TwoPair => match j_count {
+            2 => HandType::FourOfKind,
+            1 => HandType::FullHouse,
+            0 => HandType::TwoPair,
+            _ => unreachable!(),
+        },
+        HandType::OnePair => match j_count {
+            2 | 1 => HandType::ThreeOfKind,
+            0 => HandType::OnePair,
+            _ => unreachable!(),
+        },
+        HandType::HighCard => match j_count {
+            1 => HandType::OnePair,
+            0 => HandType::HighCard,
+            _ => unreachable!(),
+        },
+    }
+}
+
+pub fn part2<N: Int>(turns: &[Turn]) -> Result<N, Overflow> {
+    const POKER_ORDER: [char; 13] = [
+        'A', 'K', 'Q', 'T', '9', '8', '7', '6', '5', '4', '3', '2', 'J',
+    ];
+    let mut parsed = turns.to_vec();
+    parsed.sort_by(|a, b| {
+        let f_hand_type = modified_hand_type_with_joker(&a.hand);
+        let s_hand_type = modified_hand_type_with_joker(&b.hand);
+        match f_hand_type.cmp(&s_hand_type) {
+            std::cmp::Ordering::Equal => {
+                let (non_match_1, non_match2) = a
+                    .hand
+                    .chars()
+                    .zip(b.hand.chars())
+                    .find(|(val1, val2)| val1 != val2)
+                    .unwrap();
+                let pos1 = POKER_ORDER.iter().position(|&x| x == non_match_1).unwrap();
+                let pos2 = POKER_ORDER.iter().position(|&x| x == non_match2).unwrap();
+                pos2.cmp(&pos1)
+            }
+            _ => s_hand_type.cmp(&f_hand_type),
+        }
+    });
+
+    total(parsed.iter().map(|turn| turn.bid))
+}
+
+/// Hand type from how often each card occurs.
+fn hand_type_from_counts(hand: &str) -> HandType {
+    let mut counts = CARDS
+        .chars()
+        .map(|card| hand.matches(card).count())
+        .filter(|&count| count > 0)
+        .collect::<Vec<usize>>();
+    counts.sort_unstable_by(|a, b| b.cmp(a));
+    match counts[..] {
+        [5] => HandType::FiveOfKind,
+        [4, ..] => HandType::FourOfKind,
+        [3, 2] => HandType::FullHouse,
+        [3, ..] => HandType::ThreeOfKind,
+        [2, 2, ..] => HandType::TwoPair,
+        [2, ..] => HandType::OnePair,
+        _ => HandType::HighCard,
+    }
+}
+
+/// Total winnings of the `bids` of hands in rank order, weakest first.
+fn total<N: Int>(bids: impl IntoIterator<Item = u64>) -> Result<N, Overflow> {
+    N::try_sum(
+        bids.into_iter()
+            .enumerate()
+            .map(|(indx, bid)| N::of(indx as u64 + 1)?.try_mul(N::of(bid)?)),
+    )
+}
+
+/// Total winnings once the hands are ranked by `strength`, weakest first.
+fn winnings<K: Ord>(turns: &[Turn], strength: impl Fn(&str) -> K) -> Result<u64, Overflow> {
+    let mut turns = turns.to_vec();
+    turns.sort_by_cached_key(|turn| strength(&turn.hand));
+    total(turns.iter().map(|turn| turn.bid))
+}
+
+/// Same as [`winnings`], the hands are classified in parallel. The sort is
+/// stable, so equal hands keep their order as in the sequential version.
+fn winnings_parallel<K: Ord + Send>(
+    turns: &[Turn],
+    strength: impl Fn(&str) -> K + Sync,
+) -> Result<u64, Overflow> {
+    let mut ranked = turns
+        .par_iter()
+        .map(|turn| (strength(&turn.hand), turn.bid))
+        .collect::<Vec<(K, u64)>>();
+    ranked.par_sort_by(|a, b| a.0.cmp(&b.0));
+    total(ranked.iter().map(|&(_, bid)| bid))
+}
+
+pub fn part1_parallel(turns: &[Turn]) -> Result<u64, Overflow> {
+    winnings_parallel(turns, |hand| {
+        (
+            Reverse(find_hand_type(hand)),
+            card_values(hand, "23456789TJQKA"),
+        )
+    })
+}
+
+pub fn part2_parallel(turns: &[Turn]) -> Result<u64, Overflow> {
+    winnings_parallel(turns, |hand| {
+        (
+            Reverse(modified_hand_type_with_joker(hand)),
+            card_values(hand, "J23456789TQKA"),
+        )
+    })
+}
+
+/// Card values for breaking ties, `order` lists the cards weakest first.
+fn card_values(hand: &str, order: &str) -> Vec<usize> {
+    hand.chars().map(|card| order.find(card).unwrap()).collect()
+}
+
+/// Reference for part 1 that ranks hands by their card counts.
+pub fn part1_counts(turns: &[Turn]) -> Result<u64, Overflow> {
+    winnings(turns, |hand| {
+        (
+            Reverse(hand_type_from_counts(hand)),
+            card_values(hand, "23456789TJQKA"),
+        )
+    })
+}
+
+/// Reference for part 2 that tries every card in place of the jokers.
+pub fn part2_substitute(turns: &[Turn]) -> Result<u64, Overflow> {
+    winnings(turns, |hand| {
+        let best = CARDS
+            .chars()
+            .map(|card| hand_type_from_counts(&hand.replace('J', &card.to_string())))
+            .min()
+            .unwrap();
+        (Reverse(best), card_values(hand, "J23456789TQKA"))
+    })
+}
+
+/// `size` distinct hands with bids below 1000, at most half of all hands.
+/// The cards of a hand are drawn from one to five cards, so that every type
+/// of hand is common.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let cards = CARDS.chars().collect::<Vec<char>>();
+    let mut hands = HashSet::new();
+    let mut input = String::new();
+    while hands.len() < size.min(cards.len().pow(5) / 2) {
+        let mut pool = cards.clone();
+        rng.shuffle(&mut pool);
+        pool.truncate(rng.range(1..=5) as usize);
+        let hand = (0..5).map(|_| *rng.pick(&pool)).collect::<String>();
+        if hands.insert(hand.clone()) {
+            input.push_str(&format!("{} {}\n", hand, rng.range(1..=999)));
+        }
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use aoc_core::{check, generate::Rng, stream, Day};
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_dev_part1() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        assert_eq!(part1(&parse_input(&input).unwrap()), Ok(6440));
+    }
+
+    #[test]
+    fn test_dev_part2() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        assert_eq!(part2(&parse_input(&input).unwrap()), Ok(5905));
+    }
+
+    #[test]
+    fn test_widths() {
+        let turns = parse_input("AAAAA 4294967295\nKKKKK 4294967295\n").unwrap();
+        assert_eq!(part1::<u32>(&turns), Err(Overflow));
+        assert_eq!(part1::<u64>(&turns), Ok(3 * 4_294_967_295));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let input = "32T3K 765\nT55J5 684\n32T3K 28\n";
+        let err = parse_input(input).unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (3, 1, "a hand not dealt before")
+        );
+        let Err(stream::StreamError::Parse(err)) = stream::solve::<Day7>(input.as_bytes()) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!((err.line, err.expected.as_str()), (3, DEALT));
+        let input = "32T3K 765\n\nT55J5 684\n";
+        assert_eq!(parse_input(input).unwrap_err().line, 2);
+        let Err(stream::StreamError::Parse(err)) = stream::solve::<Day7>(input.as_bytes()) else {
+            panic!("expected a parse error");
+        };
+        assert_eq!(err.line, 2);
+    }
+
+    /// Distinct hands, as in the puzzle.
+    fn turns() -> impl Strategy<Value = String> {
+        let card = prop::sample::select(CARDS.chars().collect::<Vec<char>>());
+        let hand = prop::collection::vec(card, 5).prop_map(String::from_iter);
+        prop::collection::btree_map(hand, 1..1000u64, 1..30).prop_map(|turns| {
+            turns
+                .iter()
+                .map(|(hand, bid)| format!("{} {}\n", hand, bid))
+                .collect()
+        })
+    }
+
+    #[test]
+    fn test_implementations_agree() {
+        let day = Day::of::<Day7>();
+        check::check_fixtures(&day, Path::new("."));
+        check::cross_check(&day, Path::new("."), turns());
+    }
+
+    #[test]
+    fn test_stream() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        let parsed = parse_input(&input).unwrap();
+        assert_eq!(
+            stream::solve::<Day7>(input.as_bytes()).unwrap(),
+            (part1(&parsed), part2(&parsed))
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 500);
+        assert_eq!(input, generate(&mut Rng::new(1), 500));
+        let turns = parse_input(&input).unwrap();
+        assert_eq!(turns.len(), 500);
+        assert_eq!(part1_counts(&turns), part1(&turns));
+        assert_eq!(part2_substitute(&turns), part2(&turns));
+    }
+}
@@ -0,0 +1,510 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    error::Error,
+    fmt,
+};
+
+use aoc_core::{
+    generate::Rng,
+    num::{self, Answer, Int, Overflow, SolveError},
+    parse::{parse_all, IResult},
+    trace::{self, Event},
+    Alternative, ParseError, Part, Solution,
+};
+
+use nom::{
+    bytes::complete::{is_a, tag},
+    character::complete::{alphanumeric1, line_ending},
+    combinator::cut,
+    error::context,
+    multi::{many0_count, many1_count, separated_list1},
+    sequence::{terminated, tuple},
+};
+use rayon::prelude::*;
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Input = Game;
+    type Answer1 = Result<u64, WalkError>;
+    type Answer2 = Result<u64, WalkError>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64, WalkError> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64, WalkError> {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![
+            Alternative {
+                part: Part::Two,
+                name: "part2_parallel",
+                solve: |input| part2_parallel::<u64>(input).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_wide",
+                solve: |input| part2::<u128>(input).answer(),
+            },
+        ]
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Game {
+    moves: String,
+    source_dest_map: BTreeMap<String, (String, String)>,
+}
+
+impl Game {
+    fn new<'a>(moves: &str, nodes: impl IntoIterator<Item = (&'a str, &'a str, &'a str)>) -> Game {
+        let source_dest_map = nodes
+            .into_iter()
+            .map(|(src, left_dest, right_dest)| {
+                (
+                    src.to_string(),
+                    (left_dest.to_string(), right_dest.to_string()),
+                )
+            })
+            .collect();
+        Game {
+            moves: moves.to_string(),
+            source_dest_map,
+        }
+    }
+}
+
+/// `AAA = (BBB, CCC)`
+fn parse_node<'a>(input: &str, line: &'a str) -> Result<(&'a str, &'a str, &'a str), ParseError> {
+    let err = |rest: &str, expected: &str| ParseError::at(Day8::DAY, input, rest, expected);
+    let name = |rest: &'a str| match rest.find(|c: char| !c.is_ascii_alphanumeric()) {
+        Some(0) => Err(err(rest, "a node")),
+        Some(len) => Ok(rest.split_at(len)),
+        None if rest.is_empty() => Err(err(rest, "a node")),
+        None => Ok((rest, &rest[rest.len()..])),
+    };
+    let literal = |rest: &'a str, literal: &str| {
+        rest.strip_prefix(literal)
+            .ok_or_else(|| err(rest, &format!("`{}`", literal)))
+    };
+    let (src, rest) = name(line)?;
+    let rest = literal(rest, " = (")?;
+    let (left_dest, rest) = name(rest)?;
+    let rest = literal(rest, ", ")?;
+    let (right_dest, rest) = name(rest)?;
+    let rest = literal(rest, ")")?;
+    if !rest.is_empty() {
+        return Err(err(rest, "end of line"));
+    }
+    Ok((src, left_dest, right_dest))
+}
+
+/// Every node the network leads to must be defined in it.
+fn check_nodes(input: &str, nodes: &[(&str, &str, &str)]) -> Result<(), ParseError> {
+    let defined = nodes
+        .iter()
+        .map(|&(src, _, _)| src)
+        .collect::<HashSet<&str>>();
+    for &(_, left_dest, right_dest) in nodes {
+        for dest in [left_dest, right_dest] {
+            if !defined.contains(dest) {
+                return Err(ParseError::at(Day8::DAY, input, dest, "a defined node"));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Blank lines are skipped wherever they are, as is whitespace at the end.
+pub fn parse_input(input: &str) -> Result<Game, ParseError> {
+    let mut lines = input.trim_end().lines().filter(|line| !line.is_empty());
+    let Some(moves) = lines.next() else {
+        return Err(ParseError::new(Day8::DAY, input, 0, "a line of moves"));
+    };
+    if let Some(indx) = moves.find(|c| c != 'L' && c != 'R') {
+        return Err(ParseError::at(
+            Day8::DAY,
+            input,
+            &moves[indx..],
+            "`L` or `R`",
+        ));
+    }
+    let nodes = lines
+        .map(|line| parse_node(input, line))
+        .collect::<Result<Vec<_>, ParseError>>()?;
+    check_nodes(input, &nodes)?;
+    Ok(Game::new(moves, nodes))
+}
+
+type Network<'a> = (&'a str, Vec<(&'a str, &'a str, &'a str)>);
+
+fn parse_game(input: &str) -> IResult<'_, Network<'_>> {
+    let name = || context("a node", alphanumeric1);
+    let node = tuple((
+        terminated(name(), context("` = (`", tag(" = ("))),
+        cut(terminated(name(), context("`, `", tag(", ")))),
+        cut(terminated(name(), context("`)`", tag(")")))),
+    ));
+    let breaks = || many1_count(line_ending);
+    let (input, _) = many0_count(line_ending)(input)?;
+    let (input, moves) = context("`L` or `R`", is_a("LR"))(input)?;
+    let (input, _) = context("a line break", breaks())(input)?;
+    let (input, nodes) = separated_list1(breaks(), node)(input)?;
+    Ok((input, (moves, nodes)))
+}
+
+/// Same as [`parse_input`], built from nom parsers.
+pub fn parse_input_nom(input: &str) -> Result<Game, ParseError> {
+    let (moves, nodes) = parse_all(Day8::DAY, input, parse_game)?;
+    check_nodes(input, &nodes)?;
+    Ok(Game::new(moves, nodes))
+}
+
+/// Events of a walk through the network.
+#[derive(Debug)]
+pub enum Walk<'a> {
+    /// Standing on `node` after `step` moves of the walk from `start`.
+    Visit {
+        start: &'a str,
+        step: usize,
+        node: &'a str,
+    },
+    Move {
+        start: &'a str,
+        step: usize,
+        direction: char,
+    },
+}
+
+impl Event for Walk<'_> {
+    fn name(&self) -> &'static str {
+        match self {
+            Walk::Visit { .. } => "visit",
+            Walk::Move { .. } => "move",
+        }
+    }
+}
+
+/// Why a walk has no number of moves.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WalkError {
+    Overflow,
+    /// The walk starts from a node that is not in the network.
+    NoStart(String),
+    /// The walk from this node goes round in circles without an end.
+    Unreachable(String),
+}
+
+impl fmt::Display for WalkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WalkError::Overflow => fmt::Display::fmt(&Overflow, f),
+            WalkError::NoStart(node) => write!(f, "there is no node {}", node),
+            WalkError::Unreachable(node) => write!(f, "no end can be reached from {}", node),
+        }
+    }
+}
+
+impl Error for WalkError {}
+
+impl From<Overflow> for WalkError {
+    fn from(_: Overflow) -> Self {
+        WalkError::Overflow
+    }
+}
+
+impl From<WalkError> for SolveError {
+    fn from(err: WalkError) -> Self {
+        match err {
+            WalkError::Overflow => SolveError::Overflow,
+            _ => SolveError::NoAnswer(err.to_string()),
+        }
+    }
+}
+
+/// Number of moves from `start` to the first node that is `done`. A walk
+/// that is on the same node at the same move twice goes round in circles, so
+/// it gives up after as many moves as there are such pairs.
+fn walk(
+    game: &Game,
+    moves: &[char],
+    start: &str,
+    done: impl Fn(&str) -> bool,
+) -> Result<u64, WalkError> {
+    if !game.source_dest_map.contains_key(start) {
+        return Err(WalkError::NoStart(start.to_string()));
+    }
+    let bound = moves.len() * game.source_dest_map.len();
+    let mut curr_location = start;
+    let mut counter = 0;
+    trace::emit(|| Walk::Visit {
+        start,
+        step: counter,
+        node: curr_location,
+    });
+    while !done(curr_location) {
+        if counter == bound {
+            return Err(WalkError::Unreachable(start.to_string()));
+        }
+        let indx = counter % (moves.len());
+        let curr_move = moves[indx];
+        trace::emit(|| Walk::Move {
+            start,
+            step: counter,
+            direction: curr_move,
+        });
+        match curr_move {
+            'L' => {
+                curr_location = &game.source_dest_map[curr_location].0;
+            }
+            'R' => {
+                curr_location = &game.source_dest_map[curr_location].1;
+            }
+            _ => unreachable!(),
+        }
+        counter += 1;
+        trace::emit(|| Walk::Visit {
+            start,
+            step: counter,
+            node: curr_location,
+        });
+    }
+    Ok(counter as u64)
+}
+
+pub fn part1(game: &Game) -> Result<u64, WalkError> {
+    let moves = game.moves.chars().collect::<Vec<char>>();
+    walk(game, &moves, "AAA", |location| location == "ZZZ")
+}
+
+/// The least common multiple of `nums`, computed in `N`.
+pub fn lcm<N: Int>(nums: &[u64]) -> Result<N, Overflow> {
+    nums.iter()
+        .try_fold(N::ONE, |acc, &num| num::lcm(acc, N::of(num)?))
+}
+
+/// The nodes ending in `A`, where the ghosts start.
+fn ghosts(game: &Game) -> Vec<&str> {
+    game.source_dest_map
+        .keys()
+        .filter_map(|key| {
+            if key.ends_with('A') {
+                return Some(key.as_str());
+            }
+            None
+        })
+        .collect::<Vec<&str>>()
+}
+
+pub fn part2<N: Int>(game: &Game) -> Result<N, WalkError> {
+    let moves = game.moves.chars().collect::<Vec<char>>();
+    let source_dests = ghosts(game)
+        .iter()
+        .map(|source| walk(game, &moves, source, |location| location.ends_with('Z')))
+        .collect::<Result<Vec<u64>, WalkError>>()?;
+    Ok(lcm(source_dests.as_ref())?)
+}
+
+/// Same as [`part2`], walking the ghosts in parallel.
+pub fn part2_parallel<N: Int>(game: &Game) -> Result<N, WalkError> {
+    let moves = game.moves.chars().collect::<Vec<char>>();
+    let source_dests = ghosts(game)
+        .par_iter()
+        .map(|source| walk(game, &moves, source, |location| location.ends_with('Z')))
+        .collect::<Result<Vec<u64>, WalkError>>()?;
+    Ok(lcm(source_dests.as_ref())?)
+}
+
+/// A network of about `size` nodes, at least a thousand, in which six ghosts
+/// each walk their own cycle: from `..A` to `..Z` in some multiple of a
+/// prime steps, then from `..Z` back to it in as many. The first ghost goes
+/// from `AAA` to `ZZZ`. Every node leads to the same next node whichever way
+/// it is left, so that the cycles do not depend on the moves.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    const PRIMES: [u64; 12] = [43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97];
+    let mut primes = PRIMES;
+    rng.shuffle(&mut primes);
+    let primes = &primes[..6];
+    // The 24 * 26 * 26 names not ending in `A` or `Z` are enough for 16000.
+    let multiple = (size.min(16000) as u64 / (2 * primes.iter().sum::<u64>())).max(1);
+    let mut used = HashSet::from(["AAA".to_string(), "ZZZ".to_string()]);
+    let mut name = |rng: &mut Rng, last: Option<char>| loop {
+        let letter = |rng: &mut Rng| char::from(b'A' + rng.below(26) as u8);
+        let end = last.unwrap_or_else(|| char::from(b'B' + rng.below(24) as u8));
+        let name = format!("{}{}{}", letter(rng), letter(rng), end);
+        if used.insert(name.clone()) {
+            break name;
+        }
+    };
+    let mut nodes = vec![];
+    for (ghost, prime) in primes.iter().enumerate() {
+        let (start, end) = match ghost {
+            0 => ("AAA".to_string(), "ZZZ".to_string()),
+            _ => (name(rng, Some('A')), name(rng, Some('Z'))),
+        };
+        let pairs = (1..prime * multiple)
+            .map(|_| (name(rng, None), name(rng, None)))
+            .collect::<Vec<(String, String)>>();
+        nodes.push((start, pairs[0].clone()));
+        nodes.push((end.clone(), pairs[0].clone()));
+        for (indx, (left, right)) in pairs.iter().enumerate() {
+            let next = pairs
+                .get(indx + 1)
+                .cloned()
+                .unwrap_or_else(|| (end.clone(), end.clone()));
+            nodes.push((left.clone(), next.clone()));
+            nodes.push((right.clone(), next));
+        }
+    }
+    rng.shuffle(&mut nodes);
+    let moves = (0..rng.range(50..=300))
+        .map(|_| *rng.pick(&['L', 'R']))
+        .collect::<String>();
+    let mut input = format!("{}\n\n", moves);
+    for (node, (mut left, mut right)) in nodes {
+        if rng.one_in(2) {
+            (left, right) = (right, left);
+        }
+        input.push_str(&format!("{} = ({}, {})\n", node, left, right));
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_dev_part1() {
+        let input = fs::read_to_string("./input.dev.txt").expect("File expected");
+        let input2 = fs::read_to_string("./input.dev2.txt").expect("File expected");
+        assert_eq!(part1(&parse_input(&input).unwrap()), Ok(2));
+        assert_eq!(part1(&parse_input(&input2).unwrap()), Ok(6));
+    }
+
+    #[test]
+    fn test_dev_part2() {
+        let input = fs::read_to_string("./input.dev3.txt").expect("File expected");
+        assert_eq!(part2::<u64>(&parse_input(&input).unwrap()), Ok(6));
+    }
+
+    #[test]
+    fn test_lcm() {
+        let steps = [1 << 40, (1 << 40) + 1, 1 << 20];
+        assert_eq!(lcm::<u64>(&steps), Err(Overflow));
+        assert_eq!(lcm::<u128>(&steps), Ok((1 << 80) + (1 << 40)));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let err = parse_input("LR\n\nAAA = (BBB, CCC)\nBBB = (DDD CCC)\n").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (4, 11, "`, `")
+        );
+        let err = parse_input("LRX\n\nAAA = (BBB, CCC)\n").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (1, 3, "`L` or `R`")
+        );
+        let err = parse_input("LR\n\nAAA = (BBB, CCC)\nBBB = (AAA, AAA)\n").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (3, 13, "a defined node")
+        );
+    }
+
+    #[test]
+    fn test_nom_parser() {
+        for file in ["./input.dev.txt", "./input.dev2.txt", "./input.dev3.txt"] {
+            let input = fs::read_to_string(file).expect("File expected");
+            assert_eq!(parse_input_nom(&input), parse_input(&input));
+        }
+        for input in [
+            "LR\n\nAAA = (BBB, BBB)\n\nBBB = (ZZZ, ZZZ)\nZZZ = (ZZZ, ZZZ)\n",
+            "\nLR\nAAA = (AAA, AAA)\n\n\n",
+            "LR\r\n\r\nAAA = (AAA, AAA)\r\n",
+        ] {
+            assert!(parse_input(input).is_ok(), "{:?}", input);
+            assert_eq!(parse_input_nom(input), parse_input(input));
+        }
+        for input in [
+            "LR\n \nAAA = (AAA, AAA)\n",
+            "LR\n\nAAA = (AAA, AAA) \nBBB = (AAA, AAA)\n",
+        ] {
+            assert!(parse_input(input).is_err(), "{:?}", input);
+            assert!(parse_input_nom(input).is_err(), "{:?}", input);
+        }
+        let err = parse_input_nom("LR\n\nAAA = (BBB, CCC)\nBBB = (DDD CCC)\n").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (4, 11, "`, `")
+        );
+        let err = parse_input_nom("LR\n\nAAA = (BBB, CCC)\n").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.expected.as_str()),
+            (3, 8, "a defined node")
+        );
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let game = parse_input("LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n");
+        assert_eq!(
+            part1(&game.unwrap()),
+            Err(WalkError::Unreachable("AAA".to_string()))
+        );
+        let game = parse_input("LR\n\nBBA = (BBZ, BBZ)\nBBZ = (BBZ, BBZ)\n").unwrap();
+        assert_eq!(part1(&game), Err(WalkError::NoStart("AAA".to_string())));
+        assert_eq!(part2::<u64>(&game), Ok(1));
+    }
+
+    #[test]
+    fn test_trace() {
+        let input = fs::read_to_string("./input.dev2.txt").expect("File expected");
+        let game = parse_input(&input).unwrap();
+        let (steps, count) = trace::with(trace::Count::default(), || part1(&game));
+        assert_eq!(steps, Ok(6));
+        assert_eq!(count.0, [("move", 6), ("visit", 7)].into());
+        let (_, print) = trace::with(trace::Print(Vec::new()), || part1(&game));
+        let print = String::from_utf8(print.0).unwrap();
+        assert_eq!(
+            print.lines().take(2).collect::<Vec<&str>>(),
+            [
+                "Visit { start: \"AAA\", step: 0, node: \"AAA\" }",
+                "Move { start: \"AAA\", step: 0, direction: 'L' }"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parallel() {
+        let input = fs::read_to_string("./input.dev3.txt").expect("File expected");
+        let game = parse_input(&input).unwrap();
+        assert_eq!(part2_parallel::<u64>(&game), part2(&game));
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 2000);
+        assert_eq!(input, generate(&mut Rng::new(1), 2000));
+        let game = parse_input(&input).unwrap();
+        assert_eq!(ghosts(&game).len(), 6);
+        assert_eq!(part2::<u64>(&game).unwrap() % part1(&game).unwrap(), 0);
+        assert_eq!(part2_parallel::<u64>(&game), part2(&game));
+    }
+}
@@ -0,0 +1,310 @@
+use aoc_core::{
+    generate::Rng,
+    num::{Answer, Int, Overflow, Total},
+    parse::{numbers, parse_lines},
+    Alternative, ParseError, Part, Solution, Streaming,
+};
+
+use nom::{combinator::verify, error::context};
+use rayon::prelude::*;
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    type Input = Vec<Vec<i64>>;
+    type Answer1 = Result<i64, Overflow>;
+    type Answer2 = Result<i64, Overflow>;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<i64, Overflow> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<i64, Overflow> {
+        part2(input)
+    }
+
+    fn generate(rng: &mut Rng, size: usize) -> Option<String> {
+        Some(generate(rng, size))
+    }
+
+    fn alternatives() -> Vec<Alternative<Self::Input>> {
+        vec![
+            Alternative {
+                part: Part::Two,
+                name: "part2_reversed",
+                solve: |input| part2_reversed(input).answer(),
+            },
+            Alternative {
+                part: Part::One,
+                name: "part1_parallel",
+                solve: |input| part1_parallel(input).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_parallel",
+                solve: |input| part2_parallel(input).answer(),
+            },
+            Alternative {
+                part: Part::One,
+                name: "part1_wide",
+                solve: |input| part1::<i128>(input).answer(),
+            },
+            Alternative {
+                part: Part::Two,
+                name: "part2_wide",
+                solve: |input| part2::<i128>(input).answer(),
+            },
+        ]
+    }
+}
+
+impl Streaming for Day9 {
+    type State = (Total<i64>, Total<i64>);
+
+    fn feed(state: &mut Self::State, record: &str) -> Result<(), ParseError> {
+        let parsed = parse_input(record)?;
+        state.0.add(part1(&parsed));
+        state.1.add(part2(&parsed));
+        Ok(())
+    }
+
+    fn finish(state: Self::State) -> Result<(Self::Answer1, Self::Answer2), ParseError> {
+        Ok((state.0.get(), state.1.get()))
+    }
+}
+
+/// A sequence needs two values to have a difference.
+const SEQUENCE: &str = "at least two numbers";
+
+pub fn parse_input(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            let sequence = line
+                .split(' ')
+                .map(|num| {
+                    num.parse::<i64>()
+                        .map_err(|_| ParseError::at(Day9::DAY, input, num, "a number"))
+                })
+                .collect::<Result<Vec<i64>, ParseError>>()?;
+            if sequence.len() < 2 {
+                return Err(ParseError::at(Day9::DAY, input, line, SEQUENCE));
+            }
+            Ok(sequence)
+        })
+        .collect::<Result<Vec<_>, ParseError>>()
+}
+
+/// Same as [`parse_input`], built from nom parsers.
+pub fn parse_input_nom(input: &str) -> Result<Vec<Vec<i64>>, ParseError> {
+    parse_lines(
+        Day9::DAY,
+        input,
+        context(
+            SEQUENCE,
+            verify(numbers, |sequence: &Vec<i64>| sequence.len() >= 2),
+        ),
+    )
+}
+
+/// The sequence and its differences, down to the one that is all zeros, or
+/// else to a single value, which then counts as constant.
+fn differences<N: Int + TryFrom<i64>>(arr: &[i64]) -> Result<Vec<Vec<N>>, Overflow> {
+    let arr = arr
+        .iter()
+        .map(|&value| N::of(value))
+        .collect::<Result<Vec<N>, Overflow>>()?;
+    let mut differences: Vec<Vec<N>> = vec![arr];
+    loop {
+        let last = differences.last().unwrap();
+        if last.len() < 2 || last.iter().all(|&val| val == N::ZERO) {
+            break;
+        }
+        let difference_vec = last
+            .windows(2)
+            .map(|x| x[1].try_sub(x[0]))
+            .collect::<Result<Vec<N>, Overflow>>()?;
+        differences.push(difference_vec);
+    }
+    Ok(differences)
+}
+
+fn next_value<N: Int + TryFrom<i64>>(arr: &[i64]) -> Result<N, Overflow> {
+    N::try_sum(
+        differences::<N>(arr)?
+            .iter()
+            .rev()
+            .map(|val| Ok(*val.last().unwrap())),
+    )
+}
+
+fn previous_value<N: Int + TryFrom<i64>>(arr: &[i64]) -> Result<N, Overflow> {
+    differences::<N>(arr)?
+        .iter()
+        .rev()
+        .try_fold(N::ZERO, |acc, x| x[0].try_sub(acc))
+}
+
+pub fn part1<N: Int + TryFrom<i64>>(parsed: &[Vec<i64>]) -> Result<N, Overflow> {
+    N::try_sum(parsed.iter().map(|arr| next_value(arr)))
+}
+
+pub fn part2<N: Int + TryFrom<i64>>(parsed: &[Vec<i64>]) -> Result<N, Overflow> {
+    N::try_sum(parsed.iter().map(|arr| previous_value(arr)))
+}
+
+pub fn part1_parallel(parsed: &[Vec<i64>]) -> Result<i64, Overflow> {
+    i64::try_sum(
+        parsed
+            .par_iter()
+            .map(|arr| next_value(arr))
+            .collect::<Vec<_>>(),
+    )
+}
+
+pub fn part2_parallel(parsed: &[Vec<i64>]) -> Result<i64, Overflow> {
+    i64::try_sum(
+        parsed
+            .par_iter()
+            .map(|arr| previous_value(arr))
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Extrapolating backwards is extrapolating forwards on the reversed
+/// sequences.
+pub fn part2_reversed(parsed: &[Vec<i64>]) -> Result<i64, Overflow> {
+    let reversed = parsed
+        .iter()
+        .map(|arr| arr.iter().rev().copied().collect())
+        .collect::<Vec<Vec<i64>>>();
+    part1(&reversed)
+}
+
+/// `size` sequences of 21 values of polynomials of degree up to six with
+/// small coefficients, so that the sums stay far from overflowing.
+pub fn generate(rng: &mut Rng, size: usize) -> String {
+    let mut input = String::new();
+    for _ in 0..size {
+        let coefficients = (0..=rng.range(0..=6))
+            .map(|_| rng.range(0..=6) as i64 - 3)
+            .collect::<Vec<i64>>();
+        let values = (0..21)
+            .map(|x| {
+                let value = coefficients.iter().rev().fold(0, |acc, c| acc * x + c);
+                value.to_string()
+            })
+            .collect::<Vec<String>>();
+        input.push_str(&values.join(" "));
+        input.push('\n');
+    }
+    input
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use aoc_core::{check, generate::Rng, stream, Day};
+    use proptest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn test_dev_part1() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        assert_eq!(part1(&parse_input(&input).unwrap()), Ok(114));
+    }
+
+    #[test]
+    fn test_dev_part2() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        assert_eq!(part2(&parse_input(&input).unwrap()), Ok(2));
+    }
+
+    #[test]
+    fn test_widths() {
+        let parsed = parse_input("0 2000000000 4000000000\n").unwrap();
+        assert_eq!(part1::<i32>(&parsed), Err(Overflow));
+        assert_eq!(part1::<i64>(&parsed), Ok(6_000_000_000));
+        let parsed = parse_input("0 4000000000000000000 8000000000000000000\n").unwrap();
+        assert_eq!(part1::<i64>(&parsed), Err(Overflow));
+        assert_eq!(part1::<i128>(&parsed), Ok(12_000_000_000_000_000_000));
+    }
+
+    #[test]
+    fn test_no_zeros() {
+        // The differences run out before reaching zeros, the last one is
+        // taken as constant: 1 2 4 7.
+        let parsed = parse_input("1 2 4\n").unwrap();
+        assert_eq!(part1::<i64>(&parsed), Ok(7));
+        assert_eq!(part2::<i64>(&parsed), Ok(1));
+    }
+
+    #[test]
+    fn test_nom_parser() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        assert_eq!(parse_input_nom(&input), parse_input(&input));
+        assert_eq!(parse_input_nom("-3 4\n"), Ok(vec![vec![-3, 4]]));
+        assert_eq!(parse_input_nom("1 2\n3 x\n").unwrap_err().line, 2);
+        for parse in [parse_input, parse_input_nom] {
+            let err = parse("1 2\n5\n").unwrap_err();
+            assert_eq!(
+                (err.line, err.column, err.expected.as_str()),
+                (2, 1, "at least two numbers")
+            );
+        }
+    }
+
+    /// Values of polynomials of degree up to 3, with more values than the
+    /// degree so that the differences reach zero.
+    fn sequences() -> impl Strategy<Value = String> {
+        let sequence = (
+            prop::collection::vec(-9..10i64, 1..5),
+            0..4usize,
+            -20..20i64,
+        )
+            .prop_map(|(coefficients, extra, start)| {
+                (start..start + (coefficients.len() + 1 + extra) as i64)
+                    .map(|x| coefficients.iter().rev().fold(0, |acc, c| acc * x + c))
+                    .map(|value| value.to_string())
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            });
+        prop::collection::vec(sequence, 1..6).prop_map(|lines| lines.join("\n"))
+    }
+
+    #[test]
+    fn test_implementations_agree() {
+        let day = Day::of::<Day9>();
+        check::check_fixtures(&day, Path::new("."));
+        check::cross_check(&day, Path::new("."), sequences());
+    }
+
+    #[test]
+    fn test_stream() {
+        let input = fs::read_to_string("./input.dev.txt").expect("Expected file");
+        let parsed = parse_input(&input).unwrap();
+        assert_eq!(
+            stream::solve::<Day9>(input.as_bytes()).unwrap(),
+            (part1(&parsed), part2(&parsed))
+        );
+    }
+
+    #[test]
+    fn test_generate() {
+        let input = generate(&mut Rng::new(1), 100);
+        assert_eq!(input, generate(&mut Rng::new(1), 100));
+        let sequences = parse_input(&input).unwrap();
+        assert_eq!(sequences.len(), 100);
+        for sequence in sequences {
+            assert_eq!(next_value(&sequence[..20]), Ok(sequence[20]));
+            assert_eq!(previous_value(&sequence[1..]), Ok(sequence[0]));
+        }
+    }
+}
@@ -0,0 +1,9 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // The backtracking reference is exponential in the number of `?`.
+    aoc_fuzz::solve_streaming::<day12::Day12>(data, &["part1_backtrack"]);
+    aoc_fuzz::same_parse(data, day12::parse_input, day12::parse_input_nom);
+});
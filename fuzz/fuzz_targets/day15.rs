@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    aoc_fuzz::solve_streaming::<day15::Day15>(data, &[]);
+    aoc_fuzz::same_parse(data, day15::parse_steps, day15::parse_steps_nom);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    aoc_fuzz::solve::<day6::Day6>(data, &[]);
+    aoc_fuzz::same_parse(data, day6::parse_input, day6::parse_input_nom);
+});
@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    aoc_fuzz::solve::<day8::Day8>(data, &[]);
+    aoc_fuzz::same_parse(data, day8::parse_input, day8::parse_input_nom);
+});
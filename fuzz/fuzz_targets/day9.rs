@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    aoc_fuzz::solve_streaming::<day9::Day9>(data, &[]);
+    aoc_fuzz::same_parse(data, day9::parse_input, day9::parse_input_nom);
+});
@@ -0,0 +1,72 @@
+//! What the fuzz targets, one per day, run on each input. With cargo-fuzz
+//! and a nightly toolchain:
+//!
+//! ```text
+//! cargo +nightly fuzz run day8 -- -timeout=10
+//! ```
+//!
+//! Panics, overflows and inputs that take longer than `-timeout` seconds are
+//! saved under `fuzz/artifacts/<target>/`. `cargo +nightly fuzz run <target>
+//! <artifact>` replays one, `tests/regressions.rs` keeps those fixed.
+
+use std::{fmt::Debug, str};
+
+use aoc_core::{
+    num::{Answer, SolveError},
+    stream, Day, ParseError, Part, Solution, Streaming,
+};
+
+type Answers = (Result<String, SolveError>, Result<String, SolveError>);
+
+/// Parses `data` and, if it is a valid input, solves it with every
+/// implementation not named in `skip`, checking that those of a part agree.
+/// An overflow is not a disagreement, a wider implementation may still fit
+/// the answer, any other error is. Returns the answers of `part1` and `part2`.
+pub fn solve<S: Solution>(data: &[u8], skip: &[&str]) -> Option<Answers> {
+    let input = str::from_utf8(data).ok()?;
+    let day = Day::of::<S>();
+    let parsed = day.parse(input).ok()?;
+    let [part1, part2] = [Part::One, Part::Two].map(|part| {
+        let mut answers = day
+            .part(part)
+            .filter(|imp| !skip.contains(&imp.name))
+            .map(|imp| (imp.name, imp.solve(&parsed)));
+        let (name, answer) = answers.next().expect("part without an implementation");
+        let mut agreed = (name, answer.clone());
+        for (other, other_answer) in answers {
+            match (&agreed.1, &other_answer) {
+                (Err(SolveError::Overflow), _) => agreed = (other, other_answer),
+                (_, Err(SolveError::Overflow)) => {}
+                (answer, other_answer) => {
+                    assert_eq!(answer, other_answer, "{} and {} disagree", agreed.0, other)
+                }
+            }
+        }
+        answer
+    });
+    Some((part1, part2))
+}
+
+/// Same as [`solve`], also streaming `data`: both must give the same answers
+/// when both accept it.
+pub fn solve_streaming<S: Streaming>(data: &[u8], skip: &[&str]) {
+    let answers = solve::<S>(data, skip);
+    let streamed = stream::solve::<S>(data)
+        .ok()
+        .map(|(part1, part2)| (part1.answer(), part2.answer()));
+    if let (Some(answers), Some(streamed)) = (answers, streamed) {
+        assert_eq!(answers, streamed, "streaming gives other answers");
+    }
+}
+
+/// Checks that two parsers of a day accept the same inputs, into the same
+/// parsed form.
+pub fn same_parse<T: PartialEq + Debug>(
+    data: &[u8],
+    parse: fn(&str) -> Result<T, ParseError>,
+    other: fn(&str) -> Result<T, ParseError>,
+) {
+    if let Ok(input) = str::from_utf8(data) {
+        assert_eq!(parse(input).ok(), other(input).ok(), "parsers disagree");
+    }
+}
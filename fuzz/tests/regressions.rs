@@ -0,0 +1,84 @@
+//! Inputs the fuzz targets once crashed or hung on, replayed as their
+//! targets run them.
+
+use aoc_core::num::SolveError;
+use aoc_fuzz::{same_parse, solve, solve_streaming};
+
+#[test]
+fn day1_no_digit() {
+    solve_streaming::<day1::Day1>(b"abc\n", &[]);
+    let answers = solve::<day1::Day1>(b"eightwothree\n", &[]);
+    let no_digit = SolveError::NoAnswer("a line has no calibration digit".to_string());
+    assert_eq!(answers, Some((Err(no_digit), Ok("83".to_string()))));
+}
+
+#[test]
+fn day1_blank_line() {
+    let data = b"1\n\n2\n";
+    solve_streaming::<day1::Day1>(data, &[]);
+    assert_eq!(solve::<day1::Day1>(data, &[]), None);
+    assert!(aoc_core::stream::solve::<day1::Day1>(&data[..]).is_err());
+}
+
+#[test]
+fn day5_odd_seeds() {
+    let data = b"seeds: 79 14 55\n\nseed-to-soil map:\n50 98 2\n";
+    solve_streaming::<day5::Day5>(data, &[]);
+    assert_eq!(solve::<day5::Day5>(data, &[]), None);
+}
+
+#[test]
+fn day7_same_hand() {
+    let data = b"32T3K 765\n32T3K 28\n";
+    solve_streaming::<day7::Day7>(data, &[]);
+    assert_eq!(solve::<day7::Day7>(data, &[]), None);
+}
+
+#[test]
+fn day8_undefined_node() {
+    let data = b"LR\n\nAAA = (BBB, CCC)\n";
+    same_parse(data, day8::parse_input, day8::parse_input_nom);
+    assert_eq!(solve::<day8::Day8>(data, &[]), None);
+}
+
+#[test]
+fn day8_unreachable() {
+    let data = b"LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n";
+    let unreachable = day8::WalkError::Unreachable("AAA".to_string());
+    let game = day8::parse_input(std::str::from_utf8(data).unwrap()).unwrap();
+    assert_eq!(day8::part1(&game), Err(unreachable.clone()));
+    let answers = solve::<day8::Day8>(data, &[]);
+    assert_eq!(
+        answers.map(|answers| answers.0),
+        Some(Err(unreachable.into()))
+    );
+}
+
+#[test]
+fn day9_single_number() {
+    solve_streaming::<day9::Day9>(b"5\n", &[]);
+    same_parse(b"5\n", day9::parse_input, day9::parse_input_nom);
+    assert_eq!(solve::<day9::Day9>(b"5\n", &[]), None);
+}
+
+#[test]
+fn day13_no_mirror() {
+    let data = b"#.\n.#\n";
+    solve_streaming::<day13::Day13>(data, &[]);
+    let no_mirror = |reason: &str| Err(SolveError::NoAnswer(reason.to_string()));
+    assert_eq!(
+        solve::<day13::Day13>(data, &[]),
+        Some((
+            no_mirror("a pattern has no mirror"),
+            no_mirror("a pattern has no mirror with a smudge")
+        ))
+    );
+}
+
+#[test]
+fn day15_long_focal_length() {
+    let data = b"rn=99999999999999999999\n";
+    solve_streaming::<day15::Day15>(data, &[]);
+    same_parse(data, day15::parse_steps, day15::parse_steps_nom);
+    assert_eq!(solve::<day15::Day15>(data, &[]), None);
+}